@@ -0,0 +1,75 @@
+//! Applying block layouts to memory regions.
+//!
+//! Run with `cargo +nightly bench --features bench`.
+
+#![feature(test)]
+
+extern crate test;
+
+use norse_billow::BlockLayout;
+use std::ptr::NonNull;
+use test::{black_box, Bencher};
+
+const SIZE: usize = 1 << 16;
+
+fn layout() -> BlockLayout {
+    let (layout, _) = BlockLayout::of::<(u8, [f32; 3], u64, u16, ())>();
+    layout
+}
+
+fn wide_layout() -> BlockLayout {
+    let mut layout = BlockLayout::build();
+    for _ in 0..64 {
+        let _ = layout.add::<u32>();
+        let _ = layout.add_optional::<u16>(false);
+    }
+    layout.finish()
+}
+
+#[bench]
+fn apply(b: &mut Bencher) {
+    let layout = layout();
+    let mut data = vec![0u64; SIZE / 8];
+    let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    b.iter(|| layout.apply(black_box(data), black_box(SIZE)));
+}
+
+#[bench]
+fn apply_into(b: &mut Bencher) {
+    let layout = layout();
+    let mut data = vec![0u64; SIZE / 8];
+    let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    let mut block = layout.apply(data, SIZE);
+    b.iter(|| {
+        layout.apply_into(&mut block, black_box(data), black_box(SIZE));
+        black_box(block.len())
+    });
+}
+
+#[bench]
+fn apply_fixed(b: &mut Bencher) {
+    let layout = layout();
+    let mut data = vec![0u64; SIZE / 8];
+    let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    b.iter(|| layout.apply_fixed::<5>(black_box(data), black_box(SIZE)));
+}
+
+#[bench]
+fn apply_wide(b: &mut Bencher) {
+    let layout = wide_layout();
+    let mut data = vec![0u64; SIZE / 8];
+    let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    b.iter(|| layout.apply(black_box(data), black_box(SIZE)));
+}
+
+#[bench]
+fn apply_into_wide(b: &mut Bencher) {
+    let layout = wide_layout();
+    let mut data = vec![0u64; SIZE / 8];
+    let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    let mut block = layout.apply(data, SIZE);
+    b.iter(|| {
+        layout.apply_into(&mut block, black_box(data), black_box(SIZE));
+        black_box(block.len())
+    });
+}
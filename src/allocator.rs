@@ -0,0 +1,78 @@
+use crate::{Block, BlockLayout};
+use std::alloc::{AllocError, Allocator, Layout};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+impl BlockLayout {
+    /// Allocate a memory block for `num_elements` elements from `alloc`.
+    ///
+    /// The returned block owns the allocation and returns it to the allocator on drop.
+    /// References to allocators are allocators themselves, which allows to allocate
+    /// from borrowed arenas.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #![feature(allocator_api)]
+    /// # use norse_billow::BlockLayout;
+    /// # use std::alloc::Global;
+    /// let mut layout = BlockLayout::build();
+    /// let velocity = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let block = block_layout.allocate_in(64, Global).unwrap();
+    /// assert!(block.len() >= 64);
+    /// ```
+    pub fn allocate_in<A: Allocator>(
+        &self,
+        num_elements: usize,
+        alloc: A,
+    ) -> Result<OwnedBlockIn<A>, AllocError> {
+        let layout = self.block_layout(num_elements).map_err(|_| AllocError)?;
+        let memory = alloc.allocate(layout)?.cast::<u8>();
+        let block = unsafe { self.apply_poisoned(memory, layout.size()) };
+
+        Ok(OwnedBlockIn {
+            block,
+            memory,
+            layout,
+            alloc,
+        })
+    }
+}
+
+/// Memory block owning its allocation from an allocator
+///
+/// Obtained from [`BlockLayout::allocate_in`](struct.BlockLayout.html#method.allocate_in).
+pub struct OwnedBlockIn<A: Allocator> {
+    block: Block,
+    memory: NonNull<u8>,
+    layout: Layout,
+    alloc: A,
+}
+
+impl<A: Allocator> OwnedBlockIn<A> {
+    /// Returns the allocator of the block.
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+}
+
+impl<A: Allocator> Deref for OwnedBlockIn<A> {
+    type Target = Block;
+    fn deref(&self) -> &Block {
+        &self.block
+    }
+}
+
+impl<A: Allocator> DerefMut for OwnedBlockIn<A> {
+    fn deref_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+}
+
+impl<A: Allocator> Drop for OwnedBlockIn<A> {
+    fn drop(&mut self) {
+        unsafe { self.alloc.deallocate(self.memory, self.layout) };
+    }
+}
@@ -72,20 +72,73 @@ assert_eq!(transforms.len(), velocities.len());
 ```
 */
 
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+#[cfg(feature = "allocator_api")]
+mod allocator;
+mod owned;
+mod ragged;
+mod vec;
+
+#[cfg(feature = "allocator_api")]
+pub use crate::allocator::OwnedBlockIn;
+pub use crate::owned::OwnedBlock;
+pub use crate::ragged::{RaggedBlock, RaggedBlockLayout, RaggedLayoutBuilder};
+pub use crate::vec::SoaVec;
+
 use indexmap::IndexMap;
-use std::alloc::Layout;
-use std::ops::Range;
-use std::ptr::NonNull;
+use std::alloc::{Layout, LayoutError as LayoutErr};
+use std::any::TypeId;
+use std::array;
+use std::cell::Cell;
+use std::error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::mem::{self, MaybeUninit};
+use std::ops::{Deref, DerefMut, Range};
+use std::ptr::{self, NonNull};
 use std::slice;
+use std::sync::Arc;
+
+/// Byte pattern written to applied memory regions with the `poison` feature.
+#[cfg(all(feature = "poison", debug_assertions))]
+const POISON: u8 = 0xCD;
+
+/// Largest alignment supported for components.
+pub const MAX_ALIGNMENT: usize = 1 << 29;
 
 /// Unique handle for an array field in a layout definition.
 pub type LayoutSlot = usize;
 
+/// Drops a number of elements in place, starting at the passed pointer.
+type DropFn = unsafe fn(*mut u8, usize);
+
+/// Default value of a component, registered via `add_with_default`.
+struct DefaultValue {
+    /// Bytes of a single element, padding bytes may be uninitialized.
+    bytes: Box<[MaybeUninit<u8>]>,
+
+    /// Compares the values pointed to by two pointers to the type of the default value.
+    eq: unsafe fn(*const u8, *const u8) -> bool,
+}
+
 /// Layout builder
 pub struct LayoutBuilder {
     layouts: Vec<(LayoutSlot, Layout)>,
+    types: Vec<Option<TypeId>>,
+    defaults: Vec<Option<Arc<DefaultValue>>>,
+    drops: Vec<Option<DropFn>>,
+    shared_zst: Vec<LayoutSlot>,
+    absent: Vec<LayoutSlot>,
+    nested: Vec<(LayoutSlot, usize)>,
+    /// Requested component sizes before padding to their alignment, indexed by slot.
+    sizes: Vec<usize>,
     max_alignment: usize,
     element_size: usize,
+    /// Element stride overriding the packed element size, set via `with_element_stride`.
+    element_stride: Option<usize>,
+    max_padding_ratio: Option<f32>,
 }
 
 impl LayoutBuilder {
@@ -113,261 +166,5907 @@ impl LayoutBuilder {
     ///
     /// assert_ne!(handle_u8_0, handle_u8_1);
     /// ```
+    #[must_use]
     pub fn add<T>(&mut self) -> LayoutSlot {
-        let layout = Layout::new::<T>();
-        self.max_alignment = self.max_alignment.max(layout.align());
-        self.element_size += layout.size();
+        self.add_raw(Layout::new::<T>())
+    }
 
-        let slot = self.layouts.len();
-        self.layouts.push((slot, layout));
-        slot
+    /// Add a new typed component to the layout, chainable variant of `add`.
+    ///
+    /// The slot of the component is its insertion index, all slots can be retrieved
+    /// via `finish_with_slots`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// layout.with::<u64>().with::<[f32; 3]>().with::<u8>();
+    ///
+    /// let (block_layout, slots) = layout.finish_with_slots();
+    /// assert_eq!(slots, [0, 1, 2]);
+    /// assert_eq!(block_layout.layout().size(), 21);
+    /// ```
+    pub fn with<T>(&mut self) -> &mut Self {
+        let _ = self.add::<T>();
+        self
     }
 
-    /// Bake the layout scheme into a finalized block layout.
+    /// Add a new typed component with an increased alignment to the layout.
+    ///
+    /// The component slice will be aligned to the maximum of `align` and the alignment of `T`.
+    /// The element stride is rounded up to the alignment, keeping every element aligned.
+    /// This is useful for over-aligning components for SIMD loads.
+    ///
+    /// Note that the stride may exceed the size of `T`, components must be
+    /// accessed with a type matching the stride (e.g. a `#[repr(align)]` wrapper).
+    ///
+    /// # Panics
+    ///
+    /// `align` must be a power of two.
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use norse_billow::BlockLayout;
+    /// #[derive(Copy, Clone)]
+    /// #[repr(C, align(16))]
+    /// struct Simd([f32; 4]);
     ///
-    /// let block_layout = {
-    ///     let mut layout = BlockLayout::build();
-    ///     layout.add::<[f32; 4]>();
-    ///     layout.finish()
-    /// };
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add_aligned::<[f32; 4]>(16);
+    /// let block_layout = layout.finish();
+    ///
+    /// assert_eq!(block_layout.layout(), std::alloc::Layout::new::<Simd>());
     /// ```
-    pub fn finish(mut self) -> BlockLayout {
-        // Sort layouts to match our scheme (descending alignment).
-        self.layouts
-            .sort_by(|(slot_a, layout_a), (slot_b, layout_b)| {
-                layout_a
-                    .align()
-                    .cmp(&layout_b.align())
-                    .reverse()
-                    .then(slot_a.cmp(slot_b))
-            });
-        &self.layouts;
-        let slot_map = self
-            .layouts
-            .iter()
-            .enumerate()
-            .map(|(i, (slot, _))| (*slot, i))
-            .collect();
-        &slot_map;
-        let sub_layouts = self.layouts.into_iter().map(|(_, layout)| layout).collect();
-        let layout = Layout::from_size_align(self.element_size, self.max_alignment).unwrap();
+    #[must_use]
+    pub fn add_aligned<T>(&mut self, align: usize) -> LayoutSlot {
+        let layout = Layout::new::<T>()
+            .align_to(align)
+            .expect("invalid component alignment");
+        self.add_raw(layout)
+    }
 
-        BlockLayout {
-            slot_map,
-            layout,
-            sub_layouts,
-        }
+    /// Add a new component consisting of `count` values of type `T` per element.
+    ///
+    /// Equal to adding `[T; count]` with a runtime `count`. The values of all elements
+    /// can be accessed as one flat slice via `Block::as_grouped_slice`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the array overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let weights = layout.add_array::<f32>(4);
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0f32; 16];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 64);
+    /// assert_eq!(block.len(), 4);
+    ///
+    /// let (values, group) = unsafe { block.as_grouped_slice::<f32>(weights) };
+    /// assert_eq!(group, 4);
+    /// for weights in values.chunks_exact_mut(group) {
+    ///     weights.fill(0.25);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn add_array<T>(&mut self, count: usize) -> LayoutSlot {
+        let layout = Layout::array::<T>(count).expect("component size overflow");
+        self.add_raw(layout)
     }
-}
 
-/// SoA layout definition
-///
-/// ## Layout
-///
-/// The [`LayoutBuilder`](struct.LayoutBuilder.html) will reorder the components
-/// depending on their alignment to avoid padding. The order is deterministic.
-///
-/// The components will be order by alignment first (descending) and by insertion order
-/// for equal alignments. The resulting block layout will be aligned to the largest
-/// alignment of all components. Due to enforced power-of-two alignments for all layouts
-/// all components will be aligned and tightly packed.
-pub struct BlockLayout {
-    slot_map: IndexMap<LayoutSlot, usize>,
-    layout: Layout,
-    sub_layouts: Vec<Layout>,
-}
+    /// Add a new untyped component to the layout.
+    ///
+    /// The size of the layout is rounded up to its alignment to obtain the element stride.
+    /// Alignments above [`MAX_ALIGNMENT`](constant.MAX_ALIGNMENT.html) are rejected
+    /// when finishing the layout.
+    #[must_use]
+    pub fn add_raw(&mut self, layout: Layout) -> LayoutSlot {
+        self.sizes.push(layout.size());
+        let layout = layout.pad_to_align();
+        self.max_alignment = self.max_alignment.max(layout.align());
+        // Overflows are reported on finishing the layout.
+        self.element_size = self.element_size.saturating_add(layout.size());
 
-impl BlockLayout {
-    /// Build a new block layout.
-    pub fn build() -> LayoutBuilder {
-        LayoutBuilder {
-            layouts: Vec::new(),
-            max_alignment: 1,
-            element_size: 0,
-        }
+        let slot = self.layouts.len();
+        self.layouts.push((slot, layout));
+        self.types.push(None);
+        self.defaults.push(None);
+        self.drops.push(None);
+        slot
     }
 
-    /// Returns the layout for a single element.
+    /// Returns the element size and alignment after adding a component of type `T`.
     ///
-    /// This layout can be repeated to get the memory requirements for a specific number of elements.
-    pub fn layout(&self) -> Layout {
-        self.layout
+    /// The builder isn't modified. The element size is the sum of the padded component
+    /// sizes as in `add`, saturating on overflow. Strides set via `with_element_stride`
+    /// aren't taken into account.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u8>();
+    /// assert_eq!(layout.preview_add::<[u32; 3]>(), (13, 4));
+    ///
+    /// let _ = layout.add::<[u32; 3]>();
+    /// assert_eq!(layout.finish().layout().size(), 13);
+    /// ```
+    pub fn preview_add<T>(&self) -> (usize, usize) {
+        let layout = Layout::new::<T>().pad_to_align();
+        (
+            self.element_size.saturating_add(layout.size()),
+            self.max_alignment.max(layout.align()),
+        )
     }
 
-    /// Apply the block layout to a memory region.
-    pub fn apply(&self, data: NonNull<u8>, size: usize) -> Block {
-        if self.sub_layouts.is_empty() {
-            return Block {
-                range: 0..0,
-                len: 0,
-                slices: Vec::new(),
-            };
-        }
-
-        assert_eq!(self.layout.align() & (self.layout.align() - 1), 0); // alignment must be power-of-two
-
-        let ptr = data.as_ptr();
+    /// Add a new typed component to the layout and record its type.
+    ///
+    /// Behaves like `add`, additionally the component can be looked up by type
+    /// from applied blocks via `Block::slice_by_type`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add_typed::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 4];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 16);
+    /// unsafe {
+    ///     assert_eq!(block.slice_by_type::<u32>().unwrap().len(), 4);
+    ///     assert!(block.slice_by_type::<f32>().is_none());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn add_typed<T: 'static>(&mut self) -> LayoutSlot {
+        let slot = self.add::<T>();
+        self.types[slot] = Some(TypeId::of::<T>());
+        slot
+    }
 
-        let start = (ptr as usize + self.layout.align() - 1) & !(self.layout.align() - 1);
-        let end = (ptr as usize + size) & !(self.layout.align() - 1);
+    /// Add a new untyped component with an explicit element stride to the layout.
+    ///
+    /// Each element of the component occupies `stride` bytes and is aligned to `align`.
+    /// The component can be accessed via `Block::as_raw`.
+    ///
+    /// Fails with `LayoutError::InvalidAlignment` if `align` isn't a power of two or exceeds
+    /// [`MAX_ALIGNMENT`](constant.MAX_ALIGNMENT.html) and with `LayoutError::InvalidStride`
+    /// if the stride isn't a multiple of the alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::{BlockLayout, LayoutError};
+    /// let mut layout = BlockLayout::build();
+    /// let payload = layout.add_stride(24, 8).unwrap();
+    /// assert_eq!(
+    ///     layout.add_stride(20, 8),
+    ///     Err(LayoutError::InvalidStride { stride: 20, align: 8 })
+    /// );
+    ///
+    /// let block_layout = layout.finish();
+    /// assert_eq!(block_layout.layout().size(), 24);
+    /// ```
+    pub fn add_stride(&mut self, stride: usize, align: usize) -> Result<LayoutSlot, LayoutError> {
+        if !align.is_power_of_two() || align > MAX_ALIGNMENT {
+            return Err(LayoutError::InvalidAlignment(align));
+        }
+        if !stride.is_multiple_of(align) {
+            return Err(LayoutError::InvalidStride { stride, align });
+        }
 
-        let initial_offset = start - ptr as usize;
-        let size_aligned = end - start;
-        let len = if self.layout.size() == 0 {
-            !0
-        } else {
-            size_aligned / self.layout.size()
-        };
+        let layout = Layout::from_size_align(stride, align)?;
+        Ok(self.add_raw(layout))
+    }
 
-        let mut offset = 0;
-        let mut offsets = Vec::with_capacity(self.sub_layouts.len());
+    /// Add a new typed component with a default value to the layout.
+    ///
+    /// Behaves like `add_typed`, additionally the bytes of `default` are stored in the layout.
+    /// `Block::init_defaults` fills the component slices of applied blocks with their defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let scale = layout.add_with_default::<[f32; 2]>([1.0, 1.0]);
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u64; 4];
+    /// let mut block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 32);
+    /// unsafe {
+    ///     block.init_defaults();
+    ///     assert_eq!(block.as_slice::<[f32; 2]>(scale), [[1.0; 2]; 4]);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn add_with_default<T: Copy + PartialEq + 'static>(&mut self, default: T) -> LayoutSlot {
+        unsafe fn eq<T: PartialEq>(a: *const u8, b: *const u8) -> bool {
+            ptr::read_unaligned(a as *const T) == ptr::read_unaligned(b as *const T)
+        }
 
-        for layout in &self.sub_layouts {
-            assert_eq!(offset % layout.align(), 0);
-            offsets.push(offset);
-            offset += layout.size() * len;
+        let slot = self.add_typed::<T>();
+        let mut bytes = vec![MaybeUninit::<u8>::uninit(); mem::size_of::<T>()];
+        unsafe {
+            ptr::copy_nonoverlapping(
+                &default as *const T as *const MaybeUninit<u8>,
+                bytes.as_mut_ptr(),
+                bytes.len(),
+            );
         }
+        self.defaults[slot] = Some(Arc::new(DefaultValue {
+            bytes: bytes.into(),
+            eq: eq::<T>,
+        }));
+        slot
+    }
 
-        let mut slices = Vec::with_capacity(self.sub_layouts.len());
-        for slot in self.slot_map.values() {
-            let offset = offsets[*slot];
-            slices.push(NonNull::new(unsafe { (start as *mut u8).offset(offset as _) }).unwrap());
+    /// Add a new typed component with drop glue to the layout.
+    ///
+    /// The elements of the component can be dropped in place via `Block::drop_elements`,
+    /// which allows to store components owning resources like `String` or `Vec`.
+    /// Access the component via `Block::as_raw` or `Block::as_uninit_slice`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let name = layout.add_droppable::<String>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut block = block_layout.apply_boxed(vec![0u8; 256].into_boxed_slice());
+    /// unsafe {
+    ///     let (names, _) = block.as_raw::<String>(name);
+    ///     names.write("billow".to_string());
+    ///     names.add(1).write("norse".to_string());
+    ///     block.drop_elements(2);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn add_droppable<T: 'static>(&mut self) -> LayoutSlot {
+        unsafe fn drop_elements<T>(ptr: *mut u8, len: usize) {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr as *mut T, len));
         }
 
-        Block {
-            range: initial_offset..initial_offset + size_aligned,
-            len,
-            slices,
+        let slot = self.add::<T>();
+        if mem::needs_drop::<T>() {
+            // The type identifies the drop glue, function pointers aren't unique.
+            self.types[slot] = Some(TypeId::of::<T>());
+            self.drops[slot] = Some(drop_elements::<T>);
         }
+        slot
     }
-}
-
-/// Laid out memory block
-pub struct Block {
-    /// Memory range occupied by the block (offset).
-    range: Range<usize>,
 
-    /// Number of elements per slice.
-    len: usize,
-
-    /// Aligned pointers at the beginning of each slice.
-    slices: Vec<NonNull<u8>>,
-}
+    /// Add a new zero-sized marker component to the layout.
+    ///
+    /// Behaves like `add`, but all shared markers of a layout are coalesced into
+    /// a single physical field, which is aligned to the largest alignment of all markers.
+    /// Their slots still resolve to valid, aligned pointers.
+    ///
+    /// # Panics
+    ///
+    /// `T` must be a zero-sized type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// struct Player;
+    /// struct Visible;
+    ///
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 3]>();
+    /// let player = layout.add_shared_zst::<Player>();
+    /// let visible = layout.add_shared_zst::<Visible>();
+    ///
+    /// assert_ne!(player, visible);
+    /// ```
+    #[must_use]
+    pub fn add_shared_zst<T>(&mut self) -> LayoutSlot {
+        let layout = Layout::new::<T>();
+        assert_eq!(layout.size(), 0, "shared component must be zero-sized");
 
-impl Block {
-    //// Returns the offset range which denotes the occupied memory block.
-    pub fn range(&self) -> Range<usize> {
-        self.range.clone()
+        let slot = self.add::<T>();
+        self.shared_zst.push(slot);
+        slot
     }
 
-    /// Returns the number of elements in each individual array slice.
-    pub fn len(&self) -> usize {
-        self.len
+    /// Add a new typed component to the layout, which might be absent.
+    ///
+    /// Always returns a new slot, keeping the slot numbering stable independent of `present`.
+    /// If `present` is `true` the component is added as in `add`. Otherwise the component
+    /// doesn't occupy any memory and accessing its slot in an applied block panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 3]>();
+    /// let color = layout.add_optional::<[u8; 4]>(false);
+    /// let mass = layout.add::<f32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// assert_eq!(mass, 2);
+    /// assert!(block_layout.is_present(position));
+    /// assert!(!block_layout.is_present(color));
+    /// assert_eq!(block_layout.layout().size(), 16);
+    /// ```
+    #[must_use]
+    pub fn add_optional<T>(&mut self, present: bool) -> LayoutSlot {
+        if present {
+            return self.add::<T>();
+        }
+
+        let slot = self.add_raw(Layout::new::<()>());
+        self.absent.push(slot);
+        slot
     }
 
-    /// Get the raw pointer and len for a component slot.
+    /// Add the components of a nested layout to the layout.
     ///
-    /// # Unsafe
+    /// Returns the slot representing the nested layout, `Block::sub_block` resolves it
+    /// to a block containing the components of `inner` accessible by their slots in `inner`.
     ///
-    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// The components of `inner` are added individually and placed among all other
+    /// components of the layout, each of them with its own slice. Nested layouts may
+    /// contain nested layouts themselves. The slot of the nested layout doesn't occupy
+    /// memory, accessing it directly panics.
     ///
-    /// # Panics
+    /// # Examples
     ///
-    /// `slot` must be a valid value obtained by the corresponding block layout.
-    pub unsafe fn as_raw<T>(&self, slot: LayoutSlot) -> (*mut T, usize) {
-        let slice = &self.slices[slot];
-        (slice.cast::<T>().as_ptr(), self.len)
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut color = BlockLayout::build();
+    /// let r = color.add::<u8>();
+    /// let g = color.add::<u8>();
+    /// let b = color.add::<u8>();
+    ///
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 2]>();
+    /// let color = layout.add_nested(color.finish());
+    /// let block_layout = layout.finish();
+    /// assert_eq!(block_layout.layout().size(), 11);
+    ///
+    /// let mut data = [0u64; 16];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 128);
+    /// let colors = block.sub_block(color);
+    /// assert_eq!(colors.num_fields(), 3);
+    /// assert_eq!(colors.len(), block.len());
+    /// unsafe {
+    ///     colors.as_slice::<u8>(g).fill(255);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn add_nested(&mut self, inner: BlockLayout) -> LayoutSlot {
+        let slot = self.add_raw(Layout::new::<()>());
+        let num_slots = inner.slot_map.len();
+        self.absent.push(slot);
+        self.nested.push((slot, num_slots));
+
+        for inner_slot in 0..num_slots {
+            let outer_slot = self.add_raw(inner.sub_layout(inner_slot).unwrap());
+            self.sizes[outer_slot] = inner.sizes[inner_slot];
+            self.types[outer_slot] = inner.types[inner_slot];
+            self.defaults[outer_slot] = inner.defaults[inner_slot].clone();
+            self.drops[outer_slot] = inner.drops[inner_slot];
+            if inner.absent[inner_slot] {
+                self.absent.push(outer_slot);
+            }
+            if let Some(num_slots) = inner.nested[inner_slot] {
+                self.nested.push((outer_slot, num_slots));
+            }
+        }
+
+        slot
     }
 
-    /// Get the mutable slice for a component slot.
+    /// Remove a component from the layout.
     ///
-    /// # Unsafe
+    /// The slot stays reserved and refers to an absent component in the finished layout,
+    /// slots of the remaining components are unchanged. Removing the slot of a nested
+    /// layout removes all of its components.
     ///
-    /// The type `T` **must** match the type used on `add` for the passed slot.
-    /// All values in the resulting slice are undefined!
+    /// The physical order of the remaining components is unaffected: components with equal
+    /// alignment are ordered by their slots, which is the order they have been added in.
     ///
     /// # Panics
     ///
-    /// `slot` must be a valid value obtained by the corresponding block layout.
-    pub unsafe fn as_slice<T: Copy>(&self, slot: LayoutSlot) -> &mut [T] {
-        let slice = &self.slices[slot];
-        slice::from_raw_parts_mut(slice.cast::<T>().as_ptr(), self.len)
-    }
-}
+    /// `slot` must be a valid value obtained by this builder.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u32>();
+    /// let b = layout.add::<u64>();
+    /// layout.remove(a);
+    /// let block_layout = layout.finish();
+    ///
+    /// assert!(!block_layout.is_present(a));
+    /// assert!(block_layout.is_present(b));
+    /// assert_eq!(block_layout.layout().size(), 8);
+    /// ```
+    pub fn remove(&mut self, slot: LayoutSlot) {
+        assert!(slot < self.layouts.len(), "invalid layout slot {}", slot);
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        let num_removed = 1 + self
+            .nested
+            .iter()
+            .find(|(nested, _)| *nested == slot)
+            .map_or(0, |(_, num_slots)| *num_slots);
+        for removed in slot..slot + num_removed {
+            // Layouts are stored in slot order until the layout is finished.
+            self.layouts[removed].1 = Layout::new::<()>();
+            self.sizes[removed] = 0;
+            self.types[removed] = None;
+            self.defaults[removed] = None;
+            self.drops[removed] = None;
+            if !self.absent.contains(&removed) {
+                self.absent.push(removed);
+            }
+        }
+        self.nested
+            .retain(|(nested, _)| !(slot + 1..slot + num_removed).contains(nested));
+        self.shared_zst.retain(|shared| *shared != slot);
 
-    #[test]
-    fn empty() {
-        let layout = BlockLayout::build().finish();
-        let mut block = [0; 32];
-        layout.apply(NonNull::new(block.as_mut_ptr()).unwrap(), 32);
-    }
+        self.max_alignment = self
+            .layouts
+            .iter()
+            .map(|(_, layout)| layout.align())
+            .fold(1, usize::max);
+        self.element_size = self.layouts.iter().fold(0usize, |size, (_, layout)| {
+            size.saturating_add(layout.size())
+        });
+    }
+
+    /// Bake the layout scheme into a finalized block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    ///
+    /// let block_layout = {
+    ///     let mut layout = BlockLayout::build();
+    ///     let _ = layout.add::<[f32; 4]>();
+    ///     layout.finish()
+    /// };
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layout is invalid, see `try_finish`.
+    #[must_use]
+    pub fn finish(self) -> BlockLayout {
+        match self.try_finish() {
+            Ok(layout) => layout,
+            Err(err) => panic!("invalid block layout: {}", err),
+        }
+    }
+
+    /// Bake the layout scheme into a finalized block layout, validating the element layout.
+    ///
+    /// Fails with `LayoutError::InvalidAlignment` if a component alignment exceeds
+    /// [`MAX_ALIGNMENT`](constant.MAX_ALIGNMENT.html) and with `LayoutError::Overflow`
+    /// if the element size overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::{BlockLayout, LayoutError};
+    /// # use std::alloc::Layout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add_raw(Layout::from_size_align(1, 1 << 30).unwrap());
+    ///
+    /// assert_eq!(layout.try_finish().err(), Some(LayoutError::InvalidAlignment(1 << 30)));
+    /// ```
+    pub fn try_finish(self) -> Result<BlockLayout, LayoutError> {
+        self.finish_ordered(false)
+    }
+
+    /// Bake the layout scheme into a finalized block layout keeping the insertion order.
+    ///
+    /// In contrast to `finish` the components aren't sorted by alignment, the slices are
+    /// placed in insertion order with padding inserted between slices where required.
+    /// Adding new components to a layout doesn't move the existing components: for blocks
+    /// with the same number of elements the existing slices keep their offsets.
+    /// This allows extending persisted layouts, the prefix of the block remains readable.
+    ///
+    /// The padding reduces the number of elements fitting into a memory region compared
+    /// to a layout created via `finish`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layout is invalid, see `try_finish`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let flags = layout.add::<u8>();
+    /// let position = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish_append_stable();
+    ///
+    /// assert_eq!(block_layout.permutation(), [flags, position]);
+    /// ```
+    #[must_use]
+    pub fn finish_append_stable(self) -> BlockLayout {
+        match self.finish_ordered(true) {
+            Ok(layout) => layout,
+            Err(err) => panic!("invalid block layout: {}", err),
+        }
+    }
+
+    fn finish_ordered(mut self, stable: bool) -> Result<BlockLayout, LayoutError> {
+        if self.max_alignment > MAX_ALIGNMENT {
+            return Err(LayoutError::InvalidAlignment(self.max_alignment));
+        }
+        let element_size = match self.element_stride {
+            Some(stride) if stride < self.element_size => {
+                return Err(LayoutError::StrideTooSmall {
+                    stride,
+                    required: self.element_size,
+                });
+            }
+            Some(stride) if !stride.is_multiple_of(self.max_alignment) => {
+                return Err(LayoutError::InvalidStride {
+                    stride,
+                    align: self.max_alignment,
+                });
+            }
+            Some(stride) => stride,
+            None => self.element_size,
+        };
+        let layout = Layout::from_size_align(element_size, self.max_alignment)
+            .map_err(|_| LayoutError::Overflow)?;
+
+        // Coalesce shared zero-sized markers into a single field, represented by the first marker slot.
+        let shared_zst = mem::take(&mut self.shared_zst);
+        if let Some(&first) = shared_zst.first() {
+            let align = shared_zst
+                .iter()
+                .map(|slot| self.layouts[*slot].1.align())
+                .max()
+                .unwrap();
+            self.layouts.retain(|(slot, _)| !shared_zst.contains(slot));
+            self.layouts
+                .push((first, Layout::from_size_align(0, align).unwrap()));
+        }
+
+        if stable {
+            self.layouts.sort_by_key(|(slot, _)| *slot);
+        } else {
+            // Sort layouts to match our scheme (descending alignment).
+            self.layouts
+                .sort_by(|(slot_a, layout_a), (slot_b, layout_b)| {
+                    layout_a
+                        .align()
+                        .cmp(&layout_b.align())
+                        .reverse()
+                        .then(slot_a.cmp(slot_b))
+                });
+        }
+        let slot_map = self
+            .layouts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (slot, _))| {
+                let slots = if shared_zst.first() == Some(slot) {
+                    &shared_zst[..]
+                } else {
+                    slice::from_ref(slot)
+                };
+                slots.iter().map(move |slot| (*slot, i))
+            })
+            .collect();
+        let sub_layouts = self
+            .layouts
+            .into_iter()
+            .map(|(_, layout)| layout)
+            .collect::<Vec<_>>();
+
+        let padding = padding_bound(&sub_layouts);
+
+        // Per-slot flags, avoids searching the lists when placing slices.
+        let num_slots = self.types.len();
+        let mut absent = vec![false; num_slots];
+        for slot in self.absent {
+            absent[slot] = true;
+        }
+        let mut nested = vec![None; num_slots];
+        for (slot, num_slots) in self.nested {
+            nested[slot] = Some(num_slots);
+        }
+
+        let block_layout = BlockLayout {
+            slot_map,
+            layout,
+            padding,
+            sub_layouts,
+            types: self.types,
+            defaults: self.defaults,
+            drops: self.drops,
+            absent,
+            nested,
+            sizes: self.sizes,
+        };
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            num_fields = block_layout.slot_map.len(),
+            element_size = layout.size(),
+            align = layout.align(),
+            padding,
+            stable,
+            order = ?block_layout.permutation(),
+            "finished block layout"
+        );
+
+        Ok(block_layout)
+    }
+
+    /// Bake the layout scheme into a finalized block layout, collecting all validation errors.
+    ///
+    /// Behaves like `try_finish`, but instead of stopping at the first error all
+    /// components are validated. Reports `LayoutError::InvalidAlignment` for each
+    /// component exceeding [`MAX_ALIGNMENT`](constant.MAX_ALIGNMENT.html) in slot order,
+    /// followed by `LayoutError::Overflow` if the element size overflows.
+    /// Reports `LayoutError::ExcessivePadding` if a limit has been set via `warn_padding_over`
+    /// and the padding ratio of the finished layout exceeds it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::{BlockLayout, LayoutError};
+    /// # use std::alloc::Layout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add_raw(Layout::from_size_align(1, 1 << 30).unwrap());
+    /// let _ = layout.add::<u32>();
+    /// let _ = layout.add_raw(Layout::from_size_align(1, 1 << 31).unwrap());
+    ///
+    /// assert_eq!(
+    ///     layout.finish_checked().err().unwrap(),
+    ///     [
+    ///         LayoutError::InvalidAlignment(1 << 30),
+    ///         LayoutError::InvalidAlignment(1 << 31),
+    ///     ]
+    /// );
+    /// ```
+    pub fn finish_checked(self) -> Result<BlockLayout, Vec<LayoutError>> {
+        let mut errors = self
+            .layouts
+            .iter()
+            .map(|(_, layout)| layout.align())
+            .filter(|align| *align > MAX_ALIGNMENT)
+            .map(LayoutError::InvalidAlignment)
+            .collect::<Vec<_>>();
+
+        let size = self
+            .layouts
+            .iter()
+            .try_fold(0usize, |size, (_, layout)| size.checked_add(layout.size()));
+        let align = self.max_alignment.min(MAX_ALIGNMENT);
+        if size.is_none_or(|size| Layout::from_size_align(size, align).is_err()) {
+            errors.push(LayoutError::Overflow);
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let max_padding_ratio = self.max_padding_ratio;
+        let layout = self.try_finish().map_err(|err| vec![err])?;
+        if let Some(max_ratio) = max_padding_ratio {
+            if layout.padding_ratio() > max_ratio {
+                let stride = layout.layout.size();
+                return Err(vec![LayoutError::ExcessivePadding {
+                    padding: stride - layout.sizes.iter().sum::<usize>(),
+                    stride,
+                }]);
+            }
+        }
+
+        Ok(layout)
+    }
+
+    /// Limit the padding ratio of the layout, checked by `finish_checked`.
+    ///
+    /// See `BlockLayout::padding_ratio` for the computation of the ratio.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::{BlockLayout, LayoutError};
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add_aligned::<u8>(64);
+    /// let _ = layout.add::<u32>();
+    /// layout.warn_padding_over(0.5);
+    ///
+    /// assert_eq!(
+    ///     layout.finish_checked().err().unwrap(),
+    ///     [LayoutError::ExcessivePadding { padding: 63, stride: 68 }]
+    /// );
+    /// ```
+    pub fn warn_padding_over(&mut self, ratio: f32) -> &mut Self {
+        self.max_padding_ratio = Some(ratio);
+        self
+    }
+
+    /// Override the element stride of the layout.
+    ///
+    /// Each element occupies `stride` bytes in the memory region instead of the sum of
+    /// the component sizes. The slices stay tightly packed, the additional bytes of all
+    /// elements form unused padding at the end of the block. Allows matching the capacity
+    /// and slice offsets of foreign block formats with a fixed stride.
+    ///
+    /// Finishing the layout fails with `LayoutError::StrideTooSmall` if `stride` is smaller
+    /// than the packed element size and with `LayoutError::InvalidStride` if it isn't a
+    /// multiple of the largest component alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::{BlockLayout, LayoutError};
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 3]>();
+    /// let id = layout.add::<u32>();
+    /// layout.with_element_stride(32);
+    /// let block_layout = layout.finish();
+    /// assert_eq!(block_layout.layout().size(), 32);
+    ///
+    /// let mut data = [0u32; 32];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 128);
+    /// assert_eq!(block.len(), 4);
+    /// assert_eq!(block.physical_offsets(), [0, 48]);
+    ///
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u64>();
+    /// layout.with_element_stride(4);
+    /// assert_eq!(
+    ///     layout.try_finish().err(),
+    ///     Some(LayoutError::StrideTooSmall { stride: 4, required: 8 })
+    /// );
+    /// ```
+    pub fn with_element_stride(&mut self, stride: usize) -> &mut Self {
+        self.element_stride = Some(stride);
+        self
+    }
+
+    /// Bake the layout scheme into a finalized block layout and return all slots.
+    ///
+    /// The slots are returned in insertion order, the `i`-th added component
+    /// is stored at index `i`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::alloc::Layout;
+    /// let mut layout = BlockLayout::build();
+    /// layout.extend((0..4).map(|i| Layout::from_size_align(1 << i, 1 << i).unwrap()));
+    ///
+    /// let (block_layout, slots) = layout.finish_with_slots();
+    /// assert_eq!(slots.len(), 4);
+    /// assert_eq!(block_layout.layout().size(), 15);
+    /// ```
+    #[must_use]
+    pub fn finish_with_slots(self) -> (BlockLayout, Vec<LayoutSlot>) {
+        let slots = (0..self.layouts.len()).collect();
+        (self.finish(), slots)
+    }
+}
+
+impl Default for LayoutBuilder {
+    fn default() -> Self {
+        BlockLayout::build()
+    }
+}
+
+impl Extend<Layout> for LayoutBuilder {
+    /// Add untyped components to the layout, equal to calling `add_raw` for each layout.
+    fn extend<I: IntoIterator<Item = Layout>>(&mut self, layouts: I) {
+        for layout in layouts {
+            let _ = self.add_raw(layout);
+        }
+    }
+}
+
+/// SoA layout definition
+///
+/// ## Layout
+///
+/// The [`LayoutBuilder`](struct.LayoutBuilder.html) will reorder the components
+/// depending on their alignment to avoid padding. The order is deterministic.
+///
+/// The components will be order by alignment first (descending) and by insertion order
+/// for equal alignments. The resulting block layout will be aligned to the largest
+/// alignment of all components. Due to enforced power-of-two alignments for all layouts
+/// all components will be aligned and tightly packed.
+pub struct BlockLayout {
+    slot_map: IndexMap<LayoutSlot, usize>,
+    layout: Layout,
+    /// Upper bound of the padding bytes between slices, independent of the number of elements.
+    padding: usize,
+    sub_layouts: Vec<Layout>,
+    types: Vec<Option<TypeId>>,
+    defaults: Vec<Option<Arc<DefaultValue>>>,
+    drops: Vec<Option<DropFn>>,
+    /// Indexed by layout slot, `true` if the component doesn't occupy memory.
+    absent: Vec<bool>,
+    /// Indexed by layout slot, number of slots of the nested layout following the slot.
+    nested: Vec<Option<usize>>,
+    sizes: Vec<usize>,
+}
+
+impl BlockLayout {
+    /// Build a new block layout.
+    #[must_use]
+    pub fn build() -> LayoutBuilder {
+        LayoutBuilder {
+            layouts: Vec::new(),
+            types: Vec::new(),
+            defaults: Vec::new(),
+            drops: Vec::new(),
+            shared_zst: Vec::new(),
+            absent: Vec::new(),
+            nested: Vec::new(),
+            sizes: Vec::new(),
+            max_alignment: 1,
+            element_size: 0,
+            element_stride: None,
+            max_padding_ratio: None,
+        }
+    }
+
+    /// Build a block layout from a tuple of component types.
+    ///
+    /// Adds each type of the tuple in order as in `add` and returns the finalized
+    /// layout together with a tuple of the corresponding slots.
+    /// Tuples with up to 12 components are supported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// type Transform = [[f32; 4]; 4];
+    /// type Velocity = [f32; 3];
+    ///
+    /// let (block_layout, (transform_id, velocity_id)) = BlockLayout::of::<(Transform, Velocity)>();
+    /// assert_ne!(transform_id, velocity_id);
+    /// assert_eq!(block_layout.layout().size(), 76);
+    /// ```
+    #[must_use]
+    pub fn of<T: LayoutFields>() -> (BlockLayout, T::Slots) {
+        let mut builder = BlockLayout::build();
+        let slots = T::add_fields(&mut builder);
+        (builder.finish(), slots)
+    }
+
+    /// Compute the layout for a single element at compile time.
+    ///
+    /// `layouts` lists the component layouts in insertion order, equal to successive
+    /// calls of `add_raw` on a builder. Sizes are padded to the alignment of each
+    /// component as in `add_raw`. The result matches `layout()` of the finished block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::alloc::Layout;
+    /// # use std::ptr::NonNull;
+    /// const NUM_ELEMENTS: usize = 16;
+    /// const LAYOUTS: [Layout; 2] = [Layout::new::<u8>(), Layout::new::<[f32; 4]>()];
+    /// const SIZE: usize = BlockLayout::const_required_size(&LAYOUTS, NUM_ELEMENTS);
+    ///
+    /// let block_layout = {
+    ///     let mut layout = BlockLayout::build();
+    ///     let _ = layout.add::<u8>();
+    ///     let _ = layout.add::<[f32; 4]>();
+    ///     layout.finish()
+    /// };
+    /// assert_eq!(block_layout.layout(), BlockLayout::const_layout(&LAYOUTS));
+    ///
+    /// let mut data = [0u8; SIZE];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), SIZE);
+    /// assert!(block.len() >= NUM_ELEMENTS);
+    /// ```
+    pub const fn const_layout(layouts: &[Layout]) -> Layout {
+        let mut size = 0;
+        let mut align = 1;
+
+        let mut i = 0;
+        while i < layouts.len() {
+            size += const_padded_size(layouts[i]);
+            if layouts[i].align() > align {
+                align = layouts[i].align();
+            }
+            i += 1;
+        }
+
+        match Layout::from_size_align(size, align) {
+            Ok(layout) => layout,
+            Err(_) => panic!("invalid block layout"),
+        }
+    }
+
+    /// Compute the offset of a component slice at compile time.
+    ///
+    /// Returns the byte offset of the slice of component `index` relative to the
+    /// aligned block start for a block holding `num_elements` elements.
+    /// The ordering of the components matches the ordering applied by `finish`.
+    pub const fn const_offset(layouts: &[Layout], index: usize, num_elements: usize) -> usize {
+        let align = layouts[index].align();
+        let mut offset = 0;
+
+        let mut i = 0;
+        while i < layouts.len() {
+            let other = layouts[i].align();
+            if other > align || (other == align && i < index) {
+                offset += const_padded_size(layouts[i]) * num_elements;
+            }
+            i += 1;
+        }
+
+        offset
+    }
+
+    /// Compute the buffer size required for `num_elements` elements at compile time.
+    ///
+    /// The size accounts for the worst-case rounding of the buffer start, the resulting
+    /// buffer can hold `num_elements` elements independent of its alignment.
+    pub const fn const_required_size(layouts: &[Layout], num_elements: usize) -> usize {
+        let layout = Self::const_layout(layouts);
+        let align = layout.align();
+        let size = (layout.size() * num_elements + align - 1) & !(align - 1);
+        size + align - 1
+    }
+
+    /// Returns the layout for a single element.
+    ///
+    /// This layout can be repeated to get the memory requirements for a specific number of elements.
+    /// Layouts without components have a size of 0 and an alignment of 1.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the number of padding bytes inserted before the slice of a component.
+    ///
+    /// Components are sorted by descending alignment and their sizes are multiples of their
+    /// alignment, therefore the slices are tightly packed and no padding is required
+    /// for layouts created via `finish`. Over-aligned components added via
+    /// `add_aligned` carry their padding inside the element stride instead.
+    ///
+    /// For layouts created via `finish_append_stable` the padding depends on the number
+    /// of elements, the returned padding is the padding for blocks with a single element.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let flags = layout.add::<u8>();
+    /// let line = layout.add_aligned::<u32>(64);
+    /// let block_layout = layout.finish();
+    /// assert_eq!(block_layout.field_padding(flags), 0);
+    /// assert_eq!(block_layout.field_padding(line), 0);
+    ///
+    /// let mut data = vec![0u8; 1024];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), 1024);
+    /// let (ptr, _) = unsafe { block.as_raw::<u8>(line) };
+    /// assert_eq!(ptr as usize % 64, 0);
+    /// ```
+    pub fn field_padding(&self, slot: LayoutSlot) -> usize {
+        let physical = *self
+            .slot_map
+            .get(&slot)
+            .unwrap_or_else(|| panic!("invalid layout slot {}", slot));
+
+        let offset = self.sub_layouts[..physical]
+            .iter()
+            .map(|layout| layout.size())
+            .sum::<usize>();
+        let align = self.sub_layouts[physical].align();
+        (align - offset % align) % align
+    }
+
+    /// Returns `true` if the component of the slot occupies memory in applied blocks.
+    ///
+    /// Only components added via `add_optional` can be absent. Slots of nested layouts
+    /// are absent as well, only the components of the nested layout occupy memory.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    pub fn is_present(&self, slot: LayoutSlot) -> bool {
+        assert!(slot < self.slot_map.len(), "invalid layout slot {}", slot);
+        !self.absent[slot]
+    }
+
+    /// Returns the fraction of the element stride occupied by padding.
+    ///
+    /// Padding arises from components whose size isn't a multiple of their alignment,
+    /// e.g. components added via `add_aligned` with an increased alignment.
+    /// Returns `0.0` for layouts with a zero element size.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add_aligned::<[u8; 8]>(16);
+    /// let _ = layout.add::<[u64; 2]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// assert_eq!(block_layout.padding_ratio(), 0.25);
+    /// ```
+    pub fn padding_ratio(&self) -> f32 {
+        let stride = self.layout.size();
+        if stride == 0 {
+            return 0.0;
+        }
+        let payload = self.sizes.iter().sum::<usize>();
+        (stride - payload) as f32 / stride as f32
+    }
+
+    /// Returns the alignment required for memory regions the layout is applied to.
+    ///
+    /// This is the largest alignment of all components and equals `layout().align()`.
+    /// Components with large alignment requirements (e.g. `#[repr(align(128))]` types)
+    /// raise the alignment of the whole block.
+    ///
+    /// The allocator used for the memory region **must** honor this alignment.
+    /// Allocating with a smaller alignment (e.g. from a default-aligned allocator)
+    /// will result in lost capacity due to rounding of the block start in `apply`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// #[repr(align(128))]
+    /// struct CacheLine([u8; 128]);
+    ///
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u32>();
+    /// let _ = layout.add::<CacheLine>();
+    /// let block_layout = layout.finish();
+    ///
+    /// assert_eq!(block_layout.max_alignment(), 128);
+    /// ```
+    pub fn max_alignment(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Check the internal consistency of the layout.
+    ///
+    /// Recomputes the element layout from the component layouts and compares it
+    /// against the stored element layout, the stored element size may exceed the sum
+    /// of the component sizes for strides set via `with_element_stride`. Additionally
+    /// all slots must refer to a component and all components must be referenced by a slot.
+    ///
+    /// Layouts created by the builder are always consistent. Layouts obtained from
+    /// untrusted sources should be verified before applying them to memory.
+    pub fn verify(&self) -> Result<(), LayoutError> {
+        let mut size = 0usize;
+        let mut align = 1;
+        for layout in &self.sub_layouts {
+            size = size
+                .checked_add(layout.size())
+                .ok_or(LayoutError::Overflow)?;
+            align = align.max(layout.align());
+        }
+        if align > MAX_ALIGNMENT {
+            return Err(LayoutError::InvalidAlignment(align));
+        }
+
+        let expected = Layout::from_size_align(size, align).map_err(|_| LayoutError::Overflow)?;
+        let stride = self.layout.size();
+        if expected.align() != self.layout.align()
+            || stride < size
+            || (stride != size && !stride.is_multiple_of(align))
+        {
+            return Err(LayoutError::Mismatch {
+                expected,
+                found: self.layout,
+            });
+        }
+
+        let mut referenced = vec![false; self.sub_layouts.len()];
+        for slot in 0..self.slot_map.len() {
+            match self.slot_map.get(&slot) {
+                Some(&physical) if physical < referenced.len() => referenced[physical] = true,
+                _ => return Err(LayoutError::InvalidSlot(slot)),
+            }
+        }
+        if let Some(physical) = referenced.iter().position(|r| !r) {
+            return Err(LayoutError::Unreferenced(physical));
+        }
+
+        Ok(())
+    }
+
+    /// Check if a memory region can hold `num_elements` elements when applying the layout.
+    ///
+    /// The memory region has a size of `size` bytes and its start is aligned to at least
+    /// `ptr_align`. The check accounts for the worst-case rounding of the region
+    /// in `apply` for any start address with this alignment.
+    ///
+    /// # Panics
+    ///
+    /// `ptr_align` must be a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u64>();
+    /// let block_layout = layout.finish();
+    ///
+    /// assert!(block_layout.fits(8, 64, 8));
+    /// assert!(!block_layout.fits(4, 64, 8));
+    /// assert!(block_layout.fits(4, 68, 8));
+    /// ```
+    pub fn fits(&self, ptr_align: usize, size: usize, num_elements: usize) -> bool {
+        assert!(
+            ptr_align.is_power_of_two(),
+            "alignment must be power-of-two"
+        );
+
+        if self.sub_layouts.is_empty() {
+            return num_elements == 0;
+        }
+        if self.layout.size() == 0 {
+            return true;
+        }
+
+        let align = self.layout.align();
+        let padding = align.saturating_sub(ptr_align);
+        let size_aligned = size.saturating_sub(padding) & !(align - 1);
+        size_aligned.saturating_sub(self.padding) / self.layout.size() >= num_elements
+    }
+
+    /// Compare the components of two layouts slot by slot.
+    ///
+    /// Describes the changes required to migrate from `self` to `other`:
+    /// slots present in both layouts are either unchanged or changed (different size or alignment),
+    /// slots only present in `other` are added and slots only present in `self` are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let old = {
+    ///     let mut layout = BlockLayout::build();
+    ///     let _ = layout.add::<u32>();
+    ///     let _ = layout.add::<u16>();
+    ///     layout.finish()
+    /// };
+    /// let new = {
+    ///     let mut layout = BlockLayout::build();
+    ///     let _ = layout.add::<u32>();
+    ///     let _ = layout.add::<u64>();
+    ///     let _ = layout.add::<u8>();
+    ///     layout.finish()
+    /// };
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.unchanged, [0]);
+    /// assert_eq!(diff.changed, [1]);
+    /// assert_eq!(diff.added, [2]);
+    /// assert!(diff.removed.is_empty());
+    /// ```
+    pub fn diff(&self, other: &BlockLayout) -> LayoutDiff {
+        let mut diff = LayoutDiff::default();
+
+        let num_slots = self.slot_map.len().max(other.slot_map.len());
+        for slot in 0..num_slots {
+            match (self.sub_layout(slot), other.sub_layout(slot)) {
+                (Some(a), Some(b)) if a == b => diff.unchanged.push(slot),
+                (Some(_), Some(_)) => diff.changed.push(slot),
+                (None, Some(_)) => diff.added.push(slot),
+                (Some(_), None) => diff.removed.push(slot),
+                (None, None) => (),
+            }
+        }
+
+        diff
+    }
+
+    /// Create a layout without the component of `slot`.
+    ///
+    /// The remaining components keep their types, defaults and relative slot order,
+    /// but are assigned new consecutive slots as listed in the returned remap.
+    /// Removing the slot of a nested layout removes all of its components.
+    /// Components are ordered as in `LayoutBuilder::finish`.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by this layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u32>();
+    /// let b = layout.add::<u64>();
+    /// let c = layout.add::<u8>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let (shrunk, remap) = block_layout.without(b);
+    /// assert_eq!(shrunk.layout().size(), 5);
+    /// assert_eq!(remap.get(a), Some(0));
+    /// assert_eq!(remap.get(b), None);
+    /// assert_eq!(remap.get(c), Some(1));
+    /// ```
+    pub fn without(&self, slot: LayoutSlot) -> (BlockLayout, SlotRemap) {
+        let num_slots = self.slot_map.len();
+        assert!(slot < num_slots, "invalid layout slot {}", slot);
+
+        let num_removed = 1 + self.nested[slot].unwrap_or(0);
+        let removed = slot..slot + num_removed;
+
+        let mut builder = BlockLayout::build();
+        let mut map = Vec::with_capacity(num_slots);
+        for old_slot in 0..num_slots {
+            if removed.contains(&old_slot) {
+                map.push(None);
+                continue;
+            }
+
+            let physical = self.slot_map[&old_slot];
+            let new_slot = builder.add_raw(self.sub_layouts[physical]);
+            builder.sizes[new_slot] = self.sizes[old_slot];
+            builder.types[new_slot] = self.types[old_slot];
+            builder.defaults[new_slot] = self.defaults[old_slot].clone();
+            builder.drops[new_slot] = self.drops[old_slot];
+            if self.absent[old_slot] {
+                builder.absent.push(new_slot);
+            }
+            let shared = self
+                .slot_map
+                .iter()
+                .any(|(other, p)| *other != old_slot && *p == physical);
+            if shared {
+                builder.shared_zst.push(new_slot);
+            }
+            map.push(Some(new_slot));
+        }
+
+        for (nested, num_nested) in self.nested.iter().enumerate() {
+            if let (Some(new_slot), Some(num_nested)) = (map[nested], *num_nested) {
+                let survivors = (nested + 1..nested + 1 + num_nested)
+                    .filter(|inner| map[*inner].is_some())
+                    .count();
+                builder.nested.push((new_slot, survivors));
+            }
+        }
+
+        let layout = builder
+            .try_finish()
+            .expect("removing a component can't invalidate the layout");
+        (layout, SlotRemap { map })
+    }
+
+    fn sub_layout(&self, slot: LayoutSlot) -> Option<Layout> {
+        self.slot_map
+            .get(&slot)
+            .map(|physical| self.sub_layouts[*physical])
+    }
+
+    /// Create a view of the layout restricted to the passed slots.
+    ///
+    /// The view borrows the component layouts of this layout. Applying the view places
+    /// only the selected components in the memory region.
+    ///
+    /// # Panics
+    ///
+    /// All slots must be valid values obtained by this layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 3]>();
+    /// let velocity = layout.add::<[f32; 3]>();
+    /// let mass = layout.add::<f32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let slots = [position, mass];
+    /// let view = block_layout.view(&slots);
+    /// assert_eq!(view.layout().size(), 16);
+    ///
+    /// let mut data = [0f32; 16];
+    /// let block = view.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 64);
+    /// assert_eq!(block.len(), 4);
+    /// unsafe { block.as_slice::<f32>(mass)[0] = 1.0 };
+    /// assert_eq!(data[12], 1.0);
+    /// ```
+    pub fn view<'a>(&'a self, slots: &'a [LayoutSlot]) -> BlockLayoutRef<'a> {
+        let mut selected = vec![false; self.sub_layouts.len()];
+        for slot in slots {
+            let physical = *self
+                .slot_map
+                .get(slot)
+                .unwrap_or_else(|| panic!("invalid layout slot {}", slot));
+            selected[physical] = true;
+        }
+
+        let layouts = self
+            .sub_layouts
+            .iter()
+            .zip(&selected)
+            .filter(|(_, selected)| **selected)
+            .map(|(layout, _)| layout);
+        let size = layouts.clone().map(|layout| layout.size()).sum();
+        let align = layouts
+            .clone()
+            .map(|layout| layout.align())
+            .max()
+            .unwrap_or(1);
+
+        BlockLayoutRef {
+            parent: self,
+            slots,
+            layout: Layout::from_size_align(size, align).unwrap(),
+            padding: padding_bound(layouts),
+        }
+    }
+
+    /// Returns the slots in physical order.
+    ///
+    /// The `i`-th entry is the slot of the component placed at the `i`-th position
+    /// in memory. Shared zero-sized markers are listed next to each other.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u8>();
+    /// let b = layout.add::<u64>();
+    /// let c = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// assert_eq!(block_layout.permutation(), [b, c, a]);
+    /// assert_eq!(block_layout.inverse_permutation(), [2, 0, 1]);
+    /// ```
+    pub fn permutation(&self) -> Vec<LayoutSlot> {
+        self.slot_map.keys().copied().collect()
+    }
+
+    /// Returns the physical position for each slot.
+    ///
+    /// The `i`-th entry is the position of the component with slot `i` in memory.
+    /// Shared zero-sized markers share the same physical position.
+    pub fn inverse_permutation(&self) -> Vec<usize> {
+        (0..self.slot_map.len())
+            .map(|slot| self.slot_map[&slot])
+            .collect()
+    }
+
+    /// Returns `true` if both layouts place components of equal layouts at equal offsets.
+    ///
+    /// Compares the component layouts in physical order, ignoring the slots they have
+    /// been declared with. Blocks of structurally equivalent layouts applied to the same
+    /// memory region are byte-compatible. In contrast, `==` additionally requires
+    /// equal slots for all components.
+    ///
+    /// Components of equal alignment are placed in declaration order, declaring these
+    /// in a different order results in different offsets if their sizes differ.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let (a, _) = BlockLayout::of::<(u8, u64, [u16; 2])>();
+    /// let (b, _) = BlockLayout::of::<([u16; 2], u8, u64)>();
+    /// assert!(a.structurally_equivalent(&b));
+    /// assert!(a != b);
+    ///
+    /// let (c, _) = BlockLayout::of::<(u32, [u32; 2])>();
+    /// let (d, _) = BlockLayout::of::<([u32; 2], u32)>();
+    /// assert!(!c.structurally_equivalent(&d));
+    /// ```
+    pub fn structurally_equivalent(&self, other: &BlockLayout) -> bool {
+        self.layout == other.layout && self.sub_layouts == other.sub_layouts
+    }
+
+    /// Returns `true` if the components match the expected `(size, align)` pairs in physical order.
+    ///
+    /// Sizes are element strides of the components. Useful to assert compatibility with
+    /// a fixed table of slices expected by foreign code.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u8>();
+    /// let _ = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// assert!(block_layout.matches_fields(&[(12, 4), (1, 1)]));
+    /// assert!(!block_layout.matches_fields(&[(1, 1), (12, 4)]));
+    /// ```
+    pub fn matches_fields(&self, expected: &[(usize, usize)]) -> bool {
+        self.sub_layouts.len() == expected.len()
+            && self
+                .sub_layouts
+                .iter()
+                .zip(expected)
+                .all(|(layout, &(size, align))| layout.size() == size && layout.align() == align)
+    }
+
+    /// Returns `true` if the components of both slots are placed next to each other.
+    ///
+    /// The slots occupy consecutive physical positions with no other component in between,
+    /// in either order. Alignment padding may still separate the slices in applied blocks.
+    ///
+    /// # Panics
+    ///
+    /// Both slots must be valid values obtained by this layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u8>();
+    /// let b = layout.add::<u32>();
+    /// let c = layout.add::<u64>();
+    /// let block_layout = layout.finish();
+    ///
+    /// // Physical order: c, b, a
+    /// assert!(block_layout.are_adjacent(a, b));
+    /// assert!(block_layout.are_adjacent(c, b));
+    /// assert!(!block_layout.are_adjacent(a, c));
+    /// ```
+    pub fn are_adjacent(&self, a: LayoutSlot, b: LayoutSlot) -> bool {
+        let physical = |slot: LayoutSlot| {
+            *self
+                .slot_map
+                .get(&slot)
+                .unwrap_or_else(|| panic!("invalid layout slot {}", slot))
+        };
+        let (a, b) = (physical(a), physical(b));
+        a.max(b) - a.min(b) == 1
+    }
+
+    /// Returns the layout of a memory block holding `num_elements` elements.
+    ///
+    /// The layout can be directly passed to an allocator. Applying the block layout to
+    /// a memory region allocated with this layout results in a block with at least
+    /// `num_elements` elements. The same layout must be used for deallocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::alloc;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u64>();
+    /// let _ = layout.add::<u8>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let layout = block_layout.block_layout(100).unwrap();
+    /// unsafe {
+    ///     let memory = NonNull::new(alloc::alloc(layout)).unwrap();
+    ///     let block = block_layout.apply(memory, layout.size());
+    ///     assert!(block.len() >= 100);
+    ///     alloc::dealloc(memory.as_ptr(), layout);
+    /// }
+    /// ```
+    pub fn block_layout(&self, num_elements: usize) -> Result<Layout, LayoutError> {
+        // An overflowing size is passed on as invalid size for `Layout` to report the error.
+        let size = self
+            .layout
+            .size()
+            .saturating_mul(num_elements)
+            .saturating_add(self.padding);
+        Ok(Layout::from_size_align(size, self.layout.align())?.pad_to_align())
+    }
+
+    /// Returns the layout of an array of `num_elements` elements of a single component.
+    ///
+    /// The layout uses the element stride of the component. This is the layout required
+    /// for moving the slice of a component into a standalone allocation.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::alloc::Layout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u8>();
+    /// let position = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// assert_eq!(
+    ///     block_layout.field_array_layout(position, 10).unwrap(),
+    ///     Layout::array::<[f32; 3]>(10).unwrap(),
+    /// );
+    /// ```
+    pub fn field_array_layout(
+        &self,
+        slot: LayoutSlot,
+        num_elements: usize,
+    ) -> Result<Layout, LayoutError> {
+        let layout = self
+            .sub_layout(slot)
+            .unwrap_or_else(|| panic!("invalid layout slot {}", slot));
+        let size = layout.size().saturating_mul(num_elements);
+        Ok(Layout::from_size_align(size, layout.align())?)
+    }
+
+    /// Returns the size of an aligned memory region required to hold `num_elements` elements.
+    ///
+    /// The size is rounded up to the block alignment, memory regions aligned to
+    /// `max_alignment` with this size result in blocks with at least `num_elements` elements.
+    /// Returns 0 for layouts without components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size overflows.
+    pub fn size_for(&self, num_elements: usize) -> usize {
+        self.block_layout(num_elements)
+            .expect("block size overflow")
+            .size()
+    }
+
+    /// Returns the largest number of elements of a single block.
+    ///
+    /// Blocks of this many elements have the largest size allowed by `Layout`,
+    /// `block_layout` and `size_for` succeed for all element counts up to this limit.
+    /// Returns `usize::MAX` for layouts with a zero element size.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u64>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let max = block_layout.max_elements();
+    /// assert!(block_layout.block_layout(max).is_ok());
+    /// assert!(block_layout.block_layout(max + 1).is_err());
+    /// ```
+    pub fn max_elements(&self) -> usize {
+        let max_size = isize::MAX as usize & !(self.layout.align() - 1);
+        match self.layout.size() {
+            0 => usize::MAX,
+            size => max_size.saturating_sub(self.padding) / size,
+        }
+    }
+
+    /// Returns the smallest buffer size guaranteed to hold `num_elements` elements.
+    ///
+    /// In contrast to `size_for` the size accounts for the worst-case rounding of
+    /// the buffer start in `apply`, the buffer can hold `num_elements` elements
+    /// independent of its alignment. Equals `const_required_size` for the components
+    /// of the layout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u64>();
+    /// let _ = layout.add::<u8>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let size = block_layout.min_viable_buffer(10);
+    /// assert_eq!(size, 96 + 7);
+    /// assert!(block_layout.fits(1, size, 10));
+    /// ```
+    pub fn min_viable_buffer(&self, num_elements: usize) -> usize {
+        self.size_for(num_elements)
+            .checked_add(self.layout.align() - 1)
+            .expect("block size overflow")
+    }
+
+    /// Returns the number of elements `apply` places into an aligned memory region of `size` bytes.
+    ///
+    /// Returns 0 for layouts without components and `usize::MAX` for layouts
+    /// with only zero-sized components.
+    pub fn capacity_for(&self, size: usize) -> usize {
+        if self.sub_layouts.is_empty() {
+            0
+        } else if self.layout.size() == 0 {
+            !0
+        } else {
+            (size & !(self.layout.align() - 1)).saturating_sub(self.padding) / self.layout.size()
+        }
+    }
+
+    /// Apply the block layout to an uninitialized buffer.
+    ///
+    /// The returned block borrows the buffer, ensuring the memory outlives the block.
+    /// Component slices are handed out as `MaybeUninit` reflecting the state of the memory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::mem::MaybeUninit;
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut buffer = [MaybeUninit::<u8>::uninit(); 256];
+    /// let mut block = block_layout.apply_uninit(&mut buffer);
+    ///
+    /// for p in block.as_uninit_slice::<[f32; 3]>(position) {
+    ///     *p = MaybeUninit::new([0.0; 3]);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn apply_uninit<'a>(&self, buf: &'a mut [MaybeUninit<u8>]) -> SafeBlock<'a> {
+        let data = NonNull::new(buf.as_mut_ptr() as *mut u8).unwrap();
+        SafeBlock {
+            block: unsafe { self.apply_poisoned(data, buf.len()) },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Apply the block layout to a borrowed buffer.
+    ///
+    /// The returned block borrows the buffer mutably, the compiler ensures the memory
+    /// outlives the block and isn't accessed otherwise while the block is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let mass = layout.add::<u8>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut buffer = vec![0u8; 16];
+    /// {
+    ///     let block = block_layout.apply_borrowed(&mut buffer);
+    ///     unsafe { block.as_slice::<u8>(mass)[3] = 1 };
+    /// }
+    /// assert_eq!(buffer[3], 1);
+    /// ```
+    #[must_use]
+    pub fn apply_borrowed<'a>(&self, data: &'a mut [u8]) -> BorrowedBlock<'a> {
+        let size = data.len();
+        BorrowedBlock {
+            block: unsafe { self.apply_poisoned(NonNull::from(data).cast(), size) },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Apply the block layout to a fixed-size array holding exactly `N` elements.
+    ///
+    /// Intended for stack-backed blocks without heap allocations, the size `M` of the array
+    /// can be computed at compile time via `const_required_size`. The returned block
+    /// borrows the array and has a length of `N`.
+    ///
+    /// # Panics
+    ///
+    /// The array must be large enough to hold `N` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::alloc::Layout;
+    /// const LAYOUTS: [Layout; 2] = [Layout::new::<u16>(), Layout::new::<[f32; 2]>()];
+    /// const SIZE: usize = BlockLayout::const_required_size(&LAYOUTS, 8);
+    ///
+    /// let block_layout = {
+    ///     let mut layout = BlockLayout::build();
+    ///     let _ = layout.add::<u16>();
+    ///     let _ = layout.add::<[f32; 2]>();
+    ///     layout.finish()
+    /// };
+    ///
+    /// let mut storage = [0u8; SIZE];
+    /// let block = block_layout.apply_array::<8, SIZE>(&mut storage);
+    /// assert_eq!(block.len(), 8);
+    /// ```
+    #[must_use]
+    pub fn apply_array<'a, const N: usize, const M: usize>(
+        &self,
+        storage: &'a mut [u8; M],
+    ) -> BorrowedBlock<'a> {
+        let block = self.apply_with_len(NonNull::from(storage).cast(), M, N);
+        unsafe { block.poison() };
+        BorrowedBlock {
+            block,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Apply the block layout to the spare capacity of a vector.
+    ///
+    /// The uninitialized memory after the `len()` elements of `v` is used for the block,
+    /// for vectors created via `Vec::with_capacity` this is the whole allocation.
+    /// The returned block borrows the vector, which can't be reallocated while the block
+    /// is alive. The length of the vector isn't changed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::mem::MaybeUninit;
+    /// let mut layout = BlockLayout::build();
+    /// let id = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut memory = Vec::with_capacity(256);
+    /// let mut block = block_layout.apply_vec_capacity(&mut memory);
+    /// assert!(block.len() >= 60);
+    /// block.as_uninit_slice::<u32>(id)[0] = MaybeUninit::new(7);
+    /// ```
+    #[must_use]
+    pub fn apply_vec_capacity<'a>(&self, v: &'a mut Vec<u8>) -> SafeBlock<'a> {
+        self.apply_uninit(v.spare_capacity_mut())
+    }
+
+    /// Apply the block layout read-only to an initialized buffer.
+    ///
+    /// Zero-copy path for consuming serialized SoA data. The number of elements is
+    /// derived from the length of the buffer as in `apply_exact`, the buffer isn't modified.
+    ///
+    /// Fails with `LayoutError::UnalignedBuffer` if the buffer isn't aligned to the
+    /// alignment of the layout and with `LayoutError::SizeTooSmall` if the buffer
+    /// can't hold a single element.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::{BlockLayout, LayoutError};
+    /// let mut layout = BlockLayout::build();
+    /// let id = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let data = [1u32, 2, 3, 4];
+    /// let buf = unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, 16) };
+    ///
+    /// let block = block_layout.parse(buf).unwrap();
+    /// assert_eq!(block.len(), 4);
+    /// assert_eq!(unsafe { block.slice::<u32>(id) }, [1, 2, 3, 4]);
+    ///
+    /// assert_eq!(
+    ///     block_layout.parse(&buf[..2]).err(),
+    ///     Some(LayoutError::SizeTooSmall { required: 4, found: 2 })
+    /// );
+    /// assert_eq!(
+    ///     block_layout.parse(&buf[1..]).err(),
+    ///     Some(LayoutError::UnalignedBuffer { align: 4 })
+    /// );
+    /// ```
+    pub fn parse<'a>(&self, buf: &'a [u8]) -> Result<ReadBlock<'a>, LayoutError> {
+        let align = self.layout.align();
+        let ptr = buf.as_ptr() as usize;
+        if !ptr.is_multiple_of(align) {
+            return Err(LayoutError::UnalignedBuffer { align });
+        }
+
+        if self.layout.size() != 0 {
+            let required = self.block_layout(1)?.size();
+            if buf.len() < required {
+                return Err(LayoutError::SizeTooSmall {
+                    required,
+                    found: buf.len(),
+                });
+            }
+        }
+
+        // Placing the slices doesn't write to the memory, no poisoning for shared buffers.
+        Ok(ReadBlock {
+            block: self.apply_range(NonNull::from(buf).cast(), ptr, ptr + buf.len()),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Apply the block layout to a memory region.
+    ///
+    /// The returned block doesn't borrow the memory region, see [`Block`](struct.Block.html)
+    /// for the validity requirements. Use `apply_borrowed` or `apply_uninit` to tie the block
+    /// to the lifetime of a buffer.
+    ///
+    /// ## Poisoning
+    ///
+    /// `apply` and all other variants taking a raw memory region don't write to the memory.
+    /// With the `poison` feature enabled in builds with `debug_assertions`, the memory
+    /// covered by the slices is filled with the byte `0xCD` by `apply_poisoned` and by
+    /// the variants borrowing or owning their buffer, like `apply_borrowed`, `apply_uninit`
+    /// or `apply_boxed`. Reads of uninitialized components then show a recognizable pattern.
+    /// Previous contents of the buffer are overwritten in this case.
+    ///
+    /// ## Tracing
+    ///
+    /// With the `tracing` feature enabled, `apply`, `apply_exact` and variants delegating
+    /// to these emit a `TRACE` event with the region size, the element layout, the occupied
+    /// range and the number of elements.
+    /// Finishing a layout emits a `DEBUG` event with the number of fields, the element
+    /// layout and the physical order of the slots.
+    #[must_use]
+    pub fn apply(&self, data: NonNull<u8>, size: usize) -> Block {
+        self.apply_region(data, size, true)
+    }
+
+    /// Apply the block layout to a memory region and poison the memory of the slices.
+    ///
+    /// Behaves like `apply`, additionally fills the slices with the byte `0xCD` if the
+    /// `poison` feature is enabled in builds with `debug_assertions`.
+    ///
+    /// # Safety
+    ///
+    /// The memory region of `size` bytes at `data` **must** be valid for writes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let id = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 4];
+    /// let block = unsafe {
+    ///     block_layout.apply_poisoned(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 16)
+    /// };
+    /// let expected = if cfg!(all(feature = "poison", debug_assertions)) { 0xCDCD_CDCD } else { 0 };
+    /// assert_eq!(data, [expected; 4]);
+    /// ```
+    #[must_use]
+    pub unsafe fn apply_poisoned(&self, data: NonNull<u8>, size: usize) -> Block {
+        let block = self.apply(data, size);
+        block.poison();
+        block
+    }
+
+    /// Apply the block layout to a memory region, using all bytes after the aligned start.
+    ///
+    /// In contrast to `apply` the end of the memory region isn't rounded down to the
+    /// block alignment. The tail of the memory region is available for elements,
+    /// which might result in a larger number of elements for buffers sized exactly
+    /// to the required capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u64>();
+    /// let _ = layout.add::<u8>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u64; 4];
+    /// let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    /// assert_eq!(block_layout.apply(data, 27).len(), 2);
+    /// assert_eq!(block_layout.apply_exact(data, 27).len(), 3);
+    /// ```
+    #[must_use]
+    pub fn apply_exact(&self, data: NonNull<u8>, size: usize) -> Block {
+        self.apply_region(data, size, false)
+    }
+
+    /// Apply the block layout to a memory region given by a raw pointer.
+    ///
+    /// Returns `None` if `data` is null, otherwise equivalent to `apply`.
+    /// Only the null check is performed, a non-null pointer must still satisfy the
+    /// validity requirements of [`Block`](struct.Block.html), using an invalid
+    /// pointer results in undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 8];
+    /// let block = block_layout.apply_ptr(data.as_mut_ptr() as *mut u8, 32).unwrap();
+    /// assert_eq!(block.len(), 8);
+    /// assert!(block_layout.apply_ptr(ptr::null_mut(), 32).is_none());
+    /// ```
+    #[must_use]
+    pub fn apply_ptr(&self, data: *mut u8, size: usize) -> Option<Block> {
+        NonNull::new(data).map(|data| self.apply(data, size))
+    }
+
+    /// Apply the block layout to a memory region without allocating.
+    ///
+    /// Places the slices as in `apply`, but stores them in a
+    /// [`FixedBlock`](struct.FixedBlock.html) with a fixed number of components.
+    /// Intended for hot paths applying layouts with few components, where the heap
+    /// allocations of `Block` dominate.
+    ///
+    /// # Panics
+    ///
+    /// `FIELDS` must be equal to the number of slots of the layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let (block_layout, (position, id)) = BlockLayout::of::<([f32; 2], u8)>();
+    ///
+    /// let mut data = [0u64; 8];
+    /// let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    /// let block = block_layout.apply_fixed::<2>(data, 64);
+    /// let reference = block_layout.apply(data, 64);
+    /// assert_eq!(block.len(), reference.len());
+    /// unsafe {
+    ///     assert_eq!(block.as_raw::<u8>(id), reference.as_raw::<u8>(id));
+    ///     block.as_slice::<[f32; 2]>(position).fill([1.0, 2.0]);
+    ///     assert_eq!(reference.as_slice::<[f32; 2]>(position)[0], [1.0, 2.0]);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn apply_fixed<const FIELDS: usize>(
+        &self,
+        data: NonNull<u8>,
+        size: usize,
+    ) -> FixedBlock<FIELDS> {
+        assert_eq!(
+            self.slot_map.len(),
+            FIELDS,
+            "BlockLayout::apply_fixed: layout has {} fields",
+            self.slot_map.len()
+        );
+
+        let (start, end) = self.aligned_range(data, size, true);
+        let initial_offset = start - data.as_ptr() as usize;
+        // Empty layouts yield an empty block, see `place`.
+        let (range, len) = if FIELDS == 0 {
+            (0..0, 0)
+        } else {
+            let len = num_elements(end - start, self.layout, self.padding);
+            (initial_offset..initial_offset + (end - start), len)
+        };
+
+        // There are at most as many physical slices as slots.
+        let mut offsets = [0; FIELDS];
+        let selected = [true; FIELDS];
+        let num_physical = self.sub_layouts.len();
+        self.place_offsets(len, &selected[..num_physical], &mut offsets[..num_physical]);
+
+        let block = FixedBlock {
+            range,
+            len,
+            slices: array::from_fn(|slot| {
+                let offset = offsets[self.slot_map[&slot]];
+                self.place_slot(data, initial_offset, slot, offset)
+            }),
+        };
+        self.trace_applied(size, &block.range, block.len);
+        block
+    }
+
+    /// Apply the block layout to a memory region with a logical number of elements.
+    ///
+    /// The slices are placed according to the capacity of the memory region as in `apply`,
+    /// but the block only exposes `len` elements per slice, similar to the length
+    /// and capacity of a `Vec`.
+    ///
+    /// # Panics
+    ///
+    /// `len` must not exceed the number of elements fitting into the memory region.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 16];
+    /// let block = block_layout.apply_with_len(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 64, 4);
+    /// assert_eq!(block.len(), 4);
+    /// assert_eq!(block.capacity(), 16);
+    /// ```
+    #[must_use]
+    pub fn apply_with_len(&self, data: NonNull<u8>, size: usize, len: usize) -> Block {
+        let mut block = self.apply(data, size);
+        assert!(
+            len <= block.capacity,
+            "length {} exceeds block capacity {}",
+            len,
+            block.capacity
+        );
+        block.len = len;
+        block
+    }
+
+    /// Apply the block layout to a memory region with a known minimum alignment.
+    ///
+    /// Behaves like `apply`, but the start of the memory region is used as is,
+    /// skipping the alignment of the start pointer.
+    ///
+    /// # Panics
+    ///
+    /// `known_align` must be a power of two, not smaller than the block alignment
+    /// and `data` must be aligned to `known_align`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// #[repr(align(64))]
+    /// struct Page([u8; 256]);
+    ///
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<[f32; 4]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut page = Page([0; 256]);
+    /// let data = NonNull::new(page.0.as_mut_ptr()).unwrap();
+    /// let block = block_layout.apply_aligned(data, 256, 64);
+    /// assert_eq!(block.range(), 0..256);
+    /// assert_eq!(block.len(), 16);
+    /// ```
+    #[must_use]
+    pub fn apply_aligned(&self, data: NonNull<u8>, size: usize, known_align: usize) -> Block {
+        assert!(
+            known_align.is_power_of_two() && known_align >= self.layout.align(),
+            "known alignment {} must be a power of two of at least {}",
+            known_align,
+            self.layout.align()
+        );
+
+        let ptr = data.as_ptr() as usize;
+        assert!(
+            ptr.is_multiple_of(known_align),
+            "memory region isn't aligned to {}",
+            known_align
+        );
+
+        let end = (ptr + size) & !(self.layout.align() - 1);
+        self.apply_range(data, ptr, end)
+    }
+
+    /// Apply the block layout to a memory region, placing only the first `num_fields` components.
+    ///
+    /// Components are counted in physical order, see `permutation`. The number of elements
+    /// is derived from the full element size as in `apply`, the placed slices are at the
+    /// same offsets as in a fully applied block. The remaining components aren't mapped,
+    /// accessing their slots panics as for absent components.
+    ///
+    /// # Panics
+    ///
+    /// `num_fields` must not exceed the number of physical components.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let header = layout.add::<u64>();
+    /// let payload = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u64; 12];
+    /// let ptr = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    /// let prefix = block_layout.apply_prefix(ptr, 96, 1);
+    /// let full = block_layout.apply(ptr, 96);
+    /// assert_eq!(prefix.len(), full.len());
+    ///
+    /// // The payload isn't mapped yet.
+    /// let spans = prefix.field_spans().collect::<Vec<_>>();
+    /// assert_eq!(spans, [(header, 0, 64), (payload, 0, 0)]);
+    /// ```
+    #[must_use]
+    pub fn apply_prefix(&self, data: NonNull<u8>, size: usize, num_fields: usize) -> Block {
+        assert!(
+            num_fields <= self.sub_layouts.len(),
+            "layout has only {} fields",
+            self.sub_layouts.len()
+        );
+        let slots = self
+            .slot_map
+            .iter()
+            .filter(|(_, physical)| **physical < num_fields)
+            .map(|(slot, _)| *slot)
+            .collect::<Vec<_>>();
+
+        let (start, end) = self.aligned_range(data, size, true);
+        let block = self.place(data, start, end, self.layout, self.padding, Some(&slots));
+        self.applied(&block, size);
+        block
+    }
+
+    /// Apply the block layout to a memory region at `offset` bytes into a larger buffer.
+    ///
+    /// Behaves like `apply` for the memory region starting at `base + offset` with
+    /// a size of `size` bytes, but the range of the block is reported relative to `base`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut arena = [0u32; 64];
+    /// let base = NonNull::new(arena.as_mut_ptr() as *mut u8).unwrap();
+    /// let a = block_layout.apply_at(base, 0, 64);
+    /// let b = block_layout.apply_at(base, 66, 64);
+    /// assert_eq!(a.range(), 0..64);
+    /// assert_eq!(b.range(), 68..128);
+    /// ```
+    #[must_use]
+    pub fn apply_at(&self, base: NonNull<u8>, offset: usize, size: usize) -> Block {
+        let data = NonNull::new(base.as_ptr().wrapping_add(offset)).unwrap();
+        let mut block = self.apply(data, size);
+        block.range = block.range.start + offset..block.range.end + offset;
+        block
+    }
+
+    /// Apply the block layout to a memory region, aligning the slice of a single component.
+    ///
+    /// The start of the block and the number of elements are chosen such that the slice
+    /// of `slot` is aligned to `field_align`, preferring the largest number of elements.
+    /// The block may therefore hold fewer elements than a block from `apply`.
+    /// Returns `None` if no placement inside the memory region satisfies the alignment.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by this layout referring to a present component
+    /// and `field_align` must be a power of two.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u32>();
+    /// let hot = layout.add::<u8>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 64];
+    /// let ptr = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    /// let block = block_layout.apply_aligning_field(ptr, 256, hot, 64).unwrap();
+    /// let (hot_ptr, _) = unsafe { block.as_raw::<u8>(hot) };
+    /// assert_eq!(hot_ptr as usize % 64, 0);
+    /// ```
+    #[must_use]
+    pub fn apply_aligning_field(
+        &self,
+        data: NonNull<u8>,
+        size: usize,
+        slot: LayoutSlot,
+        field_align: usize,
+    ) -> Option<Block> {
+        assert!(
+            field_align.is_power_of_two(),
+            "field alignment {} must be a power of two",
+            field_align
+        );
+        let physical = *self
+            .slot_map
+            .get(&slot)
+            .unwrap_or_else(|| panic!("invalid layout slot {}", slot));
+
+        let align = self.layout.align();
+        let ptr = data.as_ptr() as usize;
+        let first = ptr + align_offset(data, align);
+        let end = ((ptr + size) & !(align - 1)).max(first);
+        let round = |offset: usize, align: usize| (offset + align - 1) & !(align - 1);
+
+        let stride = self.layout.size();
+        let max_len = (end - first)
+            .saturating_sub(self.padding)
+            .checked_div(stride)
+            .unwrap_or(0);
+
+        // The offset of the slice modulo `field_align` repeats after at most `field_align` elements.
+        for len in (max_len.saturating_sub(field_align)..=max_len).rev() {
+            let offset = self.sub_layouts[..physical]
+                .iter()
+                .fold(0, |offset, layout| {
+                    round(offset, layout.align()) + layout.size() * len
+                });
+            let offset = round(offset, self.sub_layouts[physical].align());
+
+            let start = round(first + offset, field_align) - offset;
+            let block_end = if stride == 0 {
+                end
+            } else {
+                start + len * stride + self.padding
+            };
+            if !start.is_multiple_of(align) || block_end > end {
+                continue;
+            }
+
+            let block = self.apply_range(data, start, block_end);
+            let _ = block.slice(slot, "BlockLayout::apply_aligning_field");
+            return Some(block);
+        }
+
+        None
+    }
+
+    /// Apply the block layout to a memory region, reusing the allocations of `block`.
+    ///
+    /// Behaves like `apply`, but updates the slices of a block previously obtained from
+    /// this layout in place instead of allocating a new block. Useful for applying the
+    /// same layout to many memory regions, e.g. per frame. All slices are rewritten from
+    /// this layout, blocks with a different number of slots and blocks of empty layouts
+    /// are replaced by a newly applied block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u8>();
+    /// let _ = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut small = [0u32; 5];
+    /// let mut large = [0u32; 40];
+    /// let mut block = block_layout.apply(NonNull::new(small.as_mut_ptr() as *mut u8).unwrap(), 20);
+    /// assert_eq!(block.len(), 4);
+    ///
+    /// let base = NonNull::new(large.as_mut_ptr() as *mut u8).unwrap();
+    /// block_layout.apply_into(&mut block, base, 160);
+    /// assert_eq!(block.len(), 32);
+    /// assert_eq!(block.physical_offsets(), [0, 128]);
+    /// let (ptr, _) = unsafe { block.as_raw::<u8>(a) };
+    /// assert_eq!(ptr, unsafe { base.as_ptr().add(128) });
+    /// ```
+    pub fn apply_into(&self, block: &mut Block, data: NonNull<u8>, size: usize) {
+        // Empty layouts yield an empty block at the start of the region, see `place`.
+        let reusable = !self.slot_map.is_empty() && block.slices.len() == self.slot_map.len();
+        if !reusable {
+            *block = self.apply(data, size);
+            return;
+        }
+
+        let (start, end) = self.aligned_range(data, size, true);
+        let initial_offset = start - data.as_ptr() as usize;
+        let size_aligned = end - start;
+        let len = num_elements(size_aligned, self.layout, self.padding);
+
+        // Slots are stored in physical order, shared zero-sized markers don't advance the offset.
+        let mut offset = 0;
+        for (&slot, &physical) in &self.slot_map {
+            let layout = self.sub_layouts[physical];
+            offset = (offset + layout.align() - 1) & !(layout.align() - 1);
+            // Blocks of other layouts may carry different types, defaults and drop glue.
+            block.slices[slot] = self.place_slot(data, initial_offset, slot, offset);
+            offset += layout.size() * len;
+        }
+
+        block.range = initial_offset..initial_offset + size_aligned;
+        block.len = len;
+        block.capacity = len;
+        block.alloc_layout = Layout::from_size_align(size_aligned, self.layout.align()).ok();
+        block.exact_end = false;
+        block.offsets.clear();
+        block
+            .offsets
+            .extend(block.slices.iter().map(|slice| slice.offset));
+        block.offsets.sort_unstable();
+
+        self.applied(block, size);
+    }
+
+    fn apply_region(&self, data: NonNull<u8>, size: usize, round_end: bool) -> Block {
+        let (start, end) = self.aligned_range(data, size, round_end);
+        let block = Block {
+            exact_end: !round_end,
+            ..self.apply_range(data, start, end)
+        };
+        self.applied(&block, size);
+        block
+    }
+
+    /// Returns the address range of the memory region at `data` the slices are placed into.
+    fn aligned_range(&self, data: NonNull<u8>, size: usize, round_end: bool) -> (usize, usize) {
+        // `Layout` guarantees power-of-two alignments, rounding the start with a mask is exact.
+        debug_assert!(self.layout.align().is_power_of_two());
+
+        let ptr = data.as_ptr() as usize;
+
+        let start = ptr + align_offset(data, self.layout.align());
+        let end = if round_end {
+            (ptr + size) & !(self.layout.align() - 1)
+        } else {
+            ptr + size
+        };
+
+        // Regions smaller than the start padding yield an empty block at `start`.
+        (start, end.max(start))
+    }
+
+    /// Shared tail of the `apply` variants, checks and reports the applied block.
+    fn applied(&self, block: &Block, size: usize) {
+        // Handing out mutable slices for different slots relies on disjoint slices.
+        debug_assert!(block.slices_disjoint(), "overlapping slices");
+        self.trace_applied(size, &block.range, block.len);
+    }
+
+    /// Emit the tracing event for an applied block.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn trace_applied(&self, size: usize, range: &Range<usize>, len: usize) {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            size,
+            element_size = self.layout.size(),
+            align = self.layout.align(),
+            start = range.start,
+            end = range.end,
+            len,
+            "applied block layout"
+        );
+    }
+
+    /// Place the slices into the aligned address range `start..end` of the memory region at `data`.
+    fn apply_range(&self, data: NonNull<u8>, start: usize, end: usize) -> Block {
+        self.place(data, start, end, self.layout, self.padding, None)
+    }
+
+    /// Place the slices of the selected slots, all slots if `selection` is `None`.
+    ///
+    /// `element` and `padding` describe the element layout and padding bound of the selection.
+    ///
+    /// Slice pointers are derived from `data` to preserve its provenance, `start` and `end`
+    /// are addresses inside the memory region.
+    fn place(
+        &self,
+        data: NonNull<u8>,
+        start: usize,
+        end: usize,
+        element: Layout,
+        padding: usize,
+        selection: Option<&[LayoutSlot]>,
+    ) -> Block {
+        let mut selected = vec![selection.is_none(); self.sub_layouts.len()];
+        let mut chosen = vec![selection.is_none(); self.slot_map.len()];
+        for &slot in selection.unwrap_or(&[]) {
+            selected[self.slot_map[&slot]] = true;
+            chosen[slot] = true;
+        }
+        // Slots outside of the selection are placed as absent slices below.
+        if self.slot_map.is_empty() {
+            return Block::new(0..0, 0, Vec::new());
+        }
+
+        let initial_offset = start - data.as_ptr() as usize;
+        let size_aligned = end - start;
+        let len = num_elements(size_aligned, element, padding);
+
+        let mut offsets = vec![0; self.sub_layouts.len()];
+        self.place_offsets(len, &selected, &mut offsets);
+
+        // Slices are indexed by layout slot, not by physical position.
+        let mut slices = Vec::with_capacity(self.sub_layouts.len());
+        for (slot, chosen) in chosen.into_iter().enumerate() {
+            if !chosen {
+                slices.push(Slice {
+                    ptr: NonNull::new(data.as_ptr().wrapping_add(initial_offset)).unwrap(),
+                    offset: 0,
+                    layout: Layout::new::<()>(),
+                    type_id: None,
+                    default: None,
+                    drop: None,
+                    absent: true,
+                    nested: None,
+                });
+                continue;
+            }
+            let offset = offsets[self.slot_map[&slot]];
+            slices.push(self.place_slot(data, initial_offset, slot, offset));
+        }
+
+        let block = Block {
+            alloc_layout: Layout::from_size_align(size_aligned, element.align()).ok(),
+            ..Block::new(initial_offset..initial_offset + size_aligned, len, slices)
+        };
+        // Handing out mutable slices for different slots relies on disjoint slices.
+        debug_assert!(block.slices_disjoint(), "overlapping slices");
+        block
+    }
+
+    /// Compute the offsets of the physical slices for `len` elements.
+    ///
+    /// Only selected physical slices occupy memory, unselected slices don't advance the offset.
+    fn place_offsets(&self, len: usize, selected: &[bool], offsets: &mut [usize]) {
+        let mut offset = 0;
+        for (physical, layout) in self.sub_layouts.iter().enumerate() {
+            if selected[physical] {
+                offset = (offset + layout.align() - 1) & !(layout.align() - 1);
+            }
+            offsets[physical] = offset;
+            if selected[physical] {
+                offset += layout.size() * len;
+            }
+        }
+    }
+
+    /// Create the slice of a slot placed at `offset` relative to the block start.
+    fn place_slot(
+        &self,
+        data: NonNull<u8>,
+        initial_offset: usize,
+        slot: LayoutSlot,
+        offset: usize,
+    ) -> Slice {
+        let physical = self.slot_map[&slot];
+        Slice {
+            ptr: NonNull::new(data.as_ptr().wrapping_add(initial_offset + offset)).unwrap(),
+            offset,
+            layout: self.sub_layouts[physical],
+            type_id: self.types[slot],
+            default: self.defaults[slot].clone(),
+            drop: self.drops[slot],
+            absent: self.absent[slot],
+            nested: self.nested[slot],
+        }
+    }
+}
+
+/// Number of elements fitting into `size_aligned` bytes, unbounded for zero-sized elements.
+fn num_elements(size_aligned: usize, element: Layout, padding: usize) -> usize {
+    if element.size() == 0 {
+        !0
+    } else {
+        size_aligned.saturating_sub(padding) / element.size()
+    }
+}
+
+/// Tuple of component types, which can be added to a layout at once
+///
+/// Used by [`BlockLayout::of`](struct.BlockLayout.html#method.of),
+/// implemented for tuples with up to 12 components.
+/// Larger tuples are rejected at compile time:
+///
+/// ```rust,compile_fail
+/// # use norse_billow::BlockLayout;
+/// let _ = BlockLayout::of::<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)>();
+/// ```
+///
+/// `COUNT` provides the number of components for
+/// [`BlockLayout::apply_fixed`](struct.BlockLayout.html#method.apply_fixed):
+///
+/// ```rust
+/// # use norse_billow::{BlockLayout, LayoutFields};
+/// # use std::ptr::NonNull;
+/// type Fields = (u32, [f32; 3], u8);
+/// const COUNT: usize = <Fields as LayoutFields>::COUNT;
+///
+/// let (block_layout, _) = BlockLayout::of::<Fields>();
+/// let mut data = [0u32; 16];
+/// let block = block_layout.apply_fixed::<COUNT>(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 64);
+/// assert_eq!(block.len(), 3);
+/// ```
+pub trait LayoutFields {
+    /// Tuple of slots with one slot per component.
+    type Slots;
+
+    /// Number of components.
+    const COUNT: usize;
+
+    /// Add all components in order to the builder.
+    fn add_fields(builder: &mut LayoutBuilder) -> Self::Slots;
+}
+
+macro_rules! impl_layout_fields {
+    ($($ty:ident),+) => {
+        impl<$($ty),+> LayoutFields for ($($ty,)+) {
+            type Slots = ($(impl_layout_fields!(@slot $ty),)+);
+
+            const COUNT: usize = [$(impl_layout_fields!(@unit $ty)),+].len();
+
+            fn add_fields(builder: &mut LayoutBuilder) -> Self::Slots {
+                ($(builder.add::<$ty>(),)+)
+            }
+        }
+    };
+    (@slot $ty:ident) => { LayoutSlot };
+    (@unit $ty:ident) => { () };
+}
+
+impl_layout_fields!(A);
+impl_layout_fields!(A, B);
+impl_layout_fields!(A, B, C);
+impl_layout_fields!(A, B, C, D);
+impl_layout_fields!(A, B, C, D, E);
+impl_layout_fields!(A, B, C, D, E, F);
+impl_layout_fields!(A, B, C, D, E, F, G);
+impl_layout_fields!(A, B, C, D, E, F, G, H);
+impl_layout_fields!(A, B, C, D, E, F, G, H, I);
+impl_layout_fields!(A, B, C, D, E, F, G, H, I, J);
+impl_layout_fields!(A, B, C, D, E, F, G, H, I, J, K);
+impl_layout_fields!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Size of `layout` rounded up to its alignment, equal to `layout.pad_to_align().size()`.
+const fn const_padded_size(layout: Layout) -> usize {
+    (layout.size() + layout.align() - 1) & !(layout.align() - 1)
+}
+
+/// Number of bytes to advance `ptr` to the next multiple of `align`.
+///
+/// Computed from the address only, pointers into the region are derived from `ptr`
+/// by adding offsets to preserve its provenance.
+pub(crate) fn align_offset(ptr: NonNull<u8>, align: usize) -> usize {
+    debug_assert!(align.is_power_of_two());
+    ptr.as_ptr().addr().wrapping_neg() & (align - 1)
+}
+
+/// Upper bound of the padding between consecutive slices of the passed component layouts.
+///
+/// Slices following only slices with larger or equal alignment don't require padding.
+fn padding_bound<'a>(layouts: impl IntoIterator<Item = &'a Layout>) -> usize {
+    let mut padding = 0usize;
+    let mut min_align = usize::MAX;
+    for layout in layouts {
+        if layout.align() > min_align {
+            padding = padding.saturating_add(layout.align() - 1);
+        }
+        min_align = min_align.min(layout.align());
+    }
+    padding
+}
+
+/// View of a block layout restricted to a subset of its slots
+///
+/// Obtained from [`BlockLayout::view`](struct.BlockLayout.html#method.view).
+/// Borrows the component layouts of the parent layout.
+pub struct BlockLayoutRef<'a> {
+    parent: &'a BlockLayout,
+    slots: &'a [LayoutSlot],
+    layout: Layout,
+    padding: usize,
+}
+
+impl<'a> BlockLayoutRef<'a> {
+    /// Returns the layout for a single element of the selected components.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the selected slots.
+    pub fn slots(&self) -> &'a [LayoutSlot] {
+        self.slots
+    }
+
+    /// Apply the selected components to a memory region.
+    ///
+    /// Behaves like `BlockLayout::apply` for a layout consisting only of the selected
+    /// components. The block uses the slots of the parent layout, slots which aren't
+    /// selected are absent.
+    #[must_use]
+    pub fn apply(&self, data: NonNull<u8>, size: usize) -> Block {
+        let align = self.layout.align();
+        let ptr = data.as_ptr() as usize;
+        let start = ptr + align_offset(data, align);
+        let end = (ptr + size) & !(align - 1);
+
+        self.parent.place(
+            data,
+            start,
+            end.max(start),
+            self.layout,
+            self.padding,
+            Some(self.slots),
+        )
+    }
+}
+
+/// Slot-wise differences between two layouts
+///
+/// Obtained from [`BlockLayout::diff`](struct.BlockLayout.html#method.diff).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutDiff {
+    /// Slots present in both layouts with equal component layouts.
+    pub unchanged: Vec<LayoutSlot>,
+    /// Slots present in both layouts with different component layouts.
+    pub changed: Vec<LayoutSlot>,
+    /// Slots only present in the other layout.
+    pub added: Vec<LayoutSlot>,
+    /// Slots only present in the original layout.
+    pub removed: Vec<LayoutSlot>,
+}
+
+/// Mapping from slots of a layout to the slots of a derived layout
+///
+/// Obtained from [`BlockLayout::without`](struct.BlockLayout.html#method.without).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotRemap {
+    map: Vec<Option<LayoutSlot>>,
+}
+
+impl SlotRemap {
+    /// Returns the new slot of `slot`, `None` if the component has been removed
+    /// or the slot is invalid.
+    pub fn get(&self, slot: LayoutSlot) -> Option<LayoutSlot> {
+        self.map.get(slot).copied().flatten()
+    }
+}
+
+/// Layouts are equal if their memory layouts are equal, that is equal components
+/// in the same physical order with the same slot assignment, and the same slots are
+/// absent or nested independent of the order of the builder calls.
+///
+/// Component types recorded via `add_typed`, `add_with_default` or `add_droppable`
+/// are compared as well. Drop glue is compared by the type of the component,
+/// default values are compared by value with the `PartialEq` implementation of their type.
+impl PartialEq for BlockLayout {
+    fn eq(&self, other: &Self) -> bool {
+        let drops_eq = self
+            .drops
+            .iter()
+            .zip(&other.drops)
+            .all(|(a, b)| a.is_some() == b.is_some());
+        // Only evaluated for equal types, defaults of a slot have the same type.
+        let defaults_eq = || {
+            self.defaults
+                .iter()
+                .zip(&other.defaults)
+                .all(|pair| match pair {
+                    (Some(a), Some(b)) => {
+                        Arc::ptr_eq(a, b)
+                            || unsafe { (a.eq)(a.bytes.as_ptr().cast(), b.bytes.as_ptr().cast()) }
+                    }
+                    (a, b) => a.is_none() && b.is_none(),
+                })
+        };
+
+        self.layout == other.layout
+            && self.sub_layouts == other.sub_layouts
+            && self.slot_map == other.slot_map
+            && self.absent == other.absent
+            && self.nested == other.nested
+            && self.types == other.types
+            && drops_eq
+            && defaults_eq()
+    }
+}
+
+impl Eq for BlockLayout {}
+
+impl Hash for BlockLayout {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.layout.hash(state);
+        self.sub_layouts.hash(state);
+        self.inverse_permutation().hash(state);
+    }
+}
+
+/// Laid out memory block borrowing its backing buffer
+///
+/// Obtained from [`BlockLayout::apply_uninit`](struct.BlockLayout.html#method.apply_uninit).
+pub struct SafeBlock<'a> {
+    block: Block,
+    _marker: PhantomData<&'a mut [MaybeUninit<u8>]>,
+}
+
+impl<'a> SafeBlock<'a> {
+    /// Get the uninitialized slice for a component slot.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    /// The size of `T` must match the size of the component and the alignment
+    /// of `T` must not exceed the alignment of the component.
+    pub fn as_uninit_slice<T>(&mut self, slot: LayoutSlot) -> &mut [MaybeUninit<T>] {
+        let slice = self.block.slice(slot, "SafeBlock::as_uninit_slice");
+        assert_eq!(mem::size_of::<T>(), slice.layout.size());
+        assert!(mem::align_of::<T>() <= slice.layout.align());
+        unsafe { slice::from_raw_parts_mut(slice.ptr.cast().as_ptr(), self.block.len) }
+    }
+}
+
+impl<'a> Deref for SafeBlock<'a> {
+    type Target = Block;
+    fn deref(&self) -> &Block {
+        &self.block
+    }
+}
+
+/// Laid out memory block mutably borrowing its initialized backing buffer
+///
+/// Obtained from [`BlockLayout::apply_borrowed`](struct.BlockLayout.html#method.apply_borrowed).
+pub struct BorrowedBlock<'a> {
+    block: Block,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> Deref for BorrowedBlock<'a> {
+    type Target = Block;
+    fn deref(&self) -> &Block {
+        &self.block
+    }
+}
+
+impl<'a> DerefMut for BorrowedBlock<'a> {
+    fn deref_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+}
+
+/// Laid out read-only view of an initialized buffer
+///
+/// Obtained from [`BlockLayout::parse`](struct.BlockLayout.html#method.parse).
+pub struct ReadBlock<'a> {
+    block: Block,
+    _marker: PhantomData<&'a [u8]>,
+}
+
+impl<'a> ReadBlock<'a> {
+    /// Returns the number of elements of each component.
+    pub fn len(&self) -> usize {
+        self.block.len
+    }
+
+    /// Returns `true` if the components hold no elements.
+    pub fn is_empty(&self) -> bool {
+        self.block.len == 0
+    }
+
+    /// Get the raw bytes of a component slot.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    pub fn bytes(&self, slot: LayoutSlot) -> &'a [u8] {
+        let slice = self.block.slice(slot, "ReadBlock::bytes");
+        unsafe { slice::from_raw_parts(slice.ptr.as_ptr(), slice.layout.size() * self.block.len) }
+    }
+
+    /// Get the typed slice of a component slot.
+    ///
+    /// # Safety
+    ///
+    /// The bytes of the component **must** be valid values of `T`.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    /// The size of `T` must match the size of the component and the alignment
+    /// of `T` must not exceed the alignment of the component.
+    pub unsafe fn slice<T>(&self, slot: LayoutSlot) -> &'a [T] {
+        let slice = self.block.slice(slot, "ReadBlock::slice");
+        assert_eq!(mem::size_of::<T>(), slice.layout.size());
+        assert!(mem::align_of::<T>() <= slice.layout.align());
+        slice::from_raw_parts(slice.ptr.cast().as_ptr(), self.block.len)
+    }
+}
+
+/// Laid out memory block with a fixed number of components
+///
+/// Obtained from [`BlockLayout::apply_fixed`](struct.BlockLayout.html#method.apply_fixed).
+/// Stores the slices inline instead of on the heap, applying a layout doesn't allocate.
+/// Only provides raw access to the slices, the validity requirements of
+/// [`Block`](struct.Block.html) apply. Accessing absent or nested slots panics
+/// as for `Block`.
+pub struct FixedBlock<const FIELDS: usize> {
+    range: Range<usize>,
+    len: usize,
+    slices: [Slice; FIELDS],
+}
+
+impl<const FIELDS: usize> FixedBlock<FIELDS> {
+    /// Returns the offset range which denotes the occupied memory block.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the number of elements in each individual array slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slices hold no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the raw pointer and len for a component slot.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    pub unsafe fn as_raw<T>(&self, slot: LayoutSlot) -> (*mut T, usize) {
+        let slice = self
+            .slice(slot, "FixedBlock::as_raw")
+            .typed::<T>(slot, "FixedBlock::as_raw");
+        (slice.ptr.cast::<T>().as_ptr(), self.len)
+    }
+
+    /// Get the mutable slice for a component slot.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// All values in the resulting slice are undefined!
+    ///
+    /// The returned slice aliases the block memory. Retrieving the same slot
+    /// multiple times concurrently results in aliasing mutable references.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_slice<T: Copy>(&self, slot: LayoutSlot) -> &mut [T] {
+        let slice = self.slice(slot, "FixedBlock::as_slice");
+        let slice = slice.typed::<T>(slot, "FixedBlock::as_slice");
+        assert_eq!(
+            mem::size_of::<T>(),
+            slice.layout.size(),
+            "FixedBlock::as_slice: size mismatch for layout slot {}",
+            slot
+        );
+        slice::from_raw_parts_mut(slice.ptr.cast::<T>().as_ptr(), self.len)
+    }
+
+    /// Get the slice of a slot, panicking with a descriptive message for invalid slots.
+    #[track_caller]
+    fn slice(&self, slot: LayoutSlot, method: &str) -> &Slice {
+        match self.slices.get(slot) {
+            Some(slice) => slice.present(slot, method),
+            None => panic!(
+                "{}: invalid layout slot {}: block has {} fields",
+                method, slot, FIELDS
+            ),
+        }
+    }
+}
+
+/// Laid out memory block
+///
+/// ## Validity
+///
+/// A block is a set of pointers into the memory region it has been applied to.
+/// It doesn't own or borrow the memory, the pointers stay valid only as long as:
+///
+/// - the memory region is neither deallocated nor reallocated, and
+/// - the memory region isn't accessed through other means while slices of the block are in use.
+///
+/// Applying a layout again to the same or an overlapping memory region doesn't invalidate
+/// existing blocks, but accessing both blocks results in overlapping slices. Only one of the
+/// blocks should be used at a time, blocks of previous applications should be dropped.
+///
+/// [`SafeBlock`](struct.SafeBlock.html) borrows the memory region and enforces these
+/// requirements at compile time.
+pub struct Block {
+    /// Memory range occupied by the block (offset).
+    range: Range<usize>,
+
+    /// Number of elements per slice.
+    len: usize,
+
+    /// Number of elements the slices have been laid out for.
+    capacity: usize,
+
+    /// Aligned slices for each component, indexed by layout slot.
+    slices: Vec<Slice>,
+
+    /// Offsets of the slices relative to the block start in physical order.
+    offsets: Vec<usize>,
+
+    /// Layout of the occupied range, recorded when applying a block layout.
+    alloc_layout: Option<Layout>,
+
+    /// The end of the memory region isn't rounded down to the alignment, see `apply_exact`.
+    exact_end: bool,
+
+    /// Borrow flags for each slice, tracking guarded slice accesses.
+    #[cfg(debug_assertions)]
+    borrows: Vec<Cell<bool>>,
+}
+
+// SAFETY: Blocks only hold pointers into the memory region without owning it. Every
+// access to the memory goes through an `unsafe` method, whose contract requires the
+// caller to uphold the validity requirements independent of the accessing thread.
+// The debug borrow flags are `Cell`s, which are `Send`. Blocks aren't `Sync`.
+unsafe impl Send for Block {}
+
+/// Array of a single component inside a block.
+#[derive(Clone)]
+struct Slice {
+    /// Aligned pointer at the beginning of the slice.
+    ptr: NonNull<u8>,
+
+    /// Offset of the slice relative to the start of the block.
+    offset: usize,
+
+    /// Layout of a single element.
+    layout: Layout,
+
+    /// Type of the component, if recorded on creation.
+    type_id: Option<TypeId>,
+
+    /// Bytes of the default value of a single element, if recorded on creation.
+    default: Option<Arc<DefaultValue>>,
+
+    /// Drop glue for a number of elements, if registered via `add_droppable`.
+    drop: Option<DropFn>,
+
+    /// The component has been added via `add_optional` without being present.
+    absent: bool,
+
+    /// Number of slots of a nested layout following this slot.
+    nested: Option<usize>,
+}
+
+impl Slice {
+    /// Panics if the slice of `slot` doesn't occupy memory.
+    #[track_caller]
+    fn present(&self, slot: LayoutSlot, method: &str) -> &Self {
+        if self.nested.is_some() {
+            panic!("{}: layout slot {} is a nested layout", method, slot);
+        }
+        if self.absent {
+            panic!("{}: layout slot {} is absent", method, slot);
+        }
+        self
+    }
+
+    /// Panics if the slice of `slot` can't be accessed with type `T`.
+    ///
+    /// Zero-sized components don't occupy memory, accessing them with a sized type
+    /// would read beyond the memory region.
+    #[track_caller]
+    fn typed<T>(&self, slot: LayoutSlot, method: &str) -> &Self {
+        assert!(
+            self.layout.size() != 0 || mem::size_of::<T>() == 0,
+            "{}: layout slot {} holds a zero-sized component",
+            method,
+            slot
+        );
+        self
+    }
+}
+
+impl Block {
+    fn new(range: Range<usize>, len: usize, slices: Vec<Slice>) -> Self {
+        let mut offsets = slices.iter().map(|slice| slice.offset).collect::<Vec<_>>();
+        offsets.sort_unstable();
+        Block {
+            range,
+            len,
+            capacity: len,
+            alloc_layout: None,
+            exact_end: false,
+            #[cfg(debug_assertions)]
+            borrows: slices.iter().map(|_| Cell::new(false)).collect(),
+            slices,
+            offsets,
+        }
+    }
+
+    /// Create a block from raw component slices.
+    ///
+    /// Each entry of `slices` describes the component of the slot equal to its index,
+    /// given by the pointer to the start of the slice and the layout of a single element.
+    /// `range` is reported as occupied memory range, it isn't used for placing the slices.
+    ///
+    /// Intended for testing code operating on blocks without applying a layout.
+    /// Only available with the `test-util` feature.
+    ///
+    /// # Safety
+    ///
+    /// Each pointer **must** be aligned to the alignment of its layout and valid for
+    /// `len` elements. The slices must not overlap. See the validity requirements of
+    /// [`Block`](struct.Block.html).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::Block;
+    /// # use std::alloc::Layout;
+    /// # use std::ptr::NonNull;
+    /// let mut positions = [[0.0f32; 2]; 4];
+    /// let mut masses = [1.0f32; 4];
+    ///
+    /// let block = unsafe {
+    ///     Block::from_parts(
+    ///         0..48,
+    ///         4,
+    ///         &[
+    ///             (NonNull::from(&mut positions).cast(), Layout::new::<[f32; 2]>()),
+    ///             (NonNull::from(&mut masses).cast(), Layout::new::<f32>()),
+    ///         ],
+    ///     )
+    /// };
+    /// assert_eq!(unsafe { block.as_slice::<f32>(1) }, [1.0; 4]);
+    /// ```
+    #[cfg(any(test, feature = "test-util"))]
+    pub unsafe fn from_parts(
+        range: Range<usize>,
+        len: usize,
+        slices: &[(NonNull<u8>, Layout)],
+    ) -> Block {
+        let base = slices
+            .iter()
+            .map(|(ptr, _)| ptr.as_ptr() as usize)
+            .min()
+            .unwrap_or(0);
+        let slices = slices
+            .iter()
+            .map(|&(ptr, layout)| {
+                debug_assert!((ptr.as_ptr() as usize).is_multiple_of(layout.align()));
+                Slice {
+                    ptr,
+                    offset: ptr.as_ptr() as usize - base,
+                    layout,
+                    type_id: None,
+                    default: None,
+                    drop: None,
+                    absent: false,
+                    nested: None,
+                }
+            })
+            .collect();
+        Block::new(range, len, slices)
+    }
+
+    //// Returns the offset range which denotes the occupied memory block.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the number of elements in each individual array slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slices contain no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the slices have been laid out for.
+    ///
+    /// Equal to `len()` unless the block has been created via `apply_with_len`.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of additional elements fitting into the slices.
+    ///
+    /// Equal to `capacity() - len()`, zero unless the block has been created via
+    /// `apply_with_len`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 16];
+    /// let data = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    /// assert_eq!(block_layout.apply_with_len(data, 64, 10).spare_capacity(), 6);
+    /// assert_eq!(block_layout.apply(data, 64).spare_capacity(), 0);
+    /// ```
+    pub fn spare_capacity(&self) -> usize {
+        self.capacity - self.len
+    }
+
+    /// Returns the memory layout of the occupied range of the block.
+    ///
+    /// The layout is recorded when applying a block layout: the size is the length of
+    /// `range()`, the alignment is the alignment of the applied layout. For blocks applied
+    /// to memory allocated with `BlockLayout::block_layout` and the size of this allocation,
+    /// the layout equals the allocation layout and can be passed to `dealloc` together with
+    /// the allocated pointer. Blocks applied to unaligned memory regions occupy only a part
+    /// of the region, the layout doesn't describe the region in this case.
+    ///
+    /// Returns `None` for blocks derived from other blocks, e.g. via `chunks`, `sub_region`,
+    /// `split_fields` or `sub_block`, for blocks of empty layouts and for blocks created
+    /// from raw parts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::alloc;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u64>();
+    /// let _ = layout.add::<u8>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let layout = block_layout.block_layout(10).unwrap();
+    /// let memory = unsafe { alloc::alloc(layout) };
+    /// let block = block_layout.apply(NonNull::new(memory).unwrap(), layout.size());
+    /// assert_eq!(block.alloc_layout(), Some(layout));
+    /// assert_eq!(block.sub_region(0, 5).alloc_layout(), None);
+    ///
+    /// unsafe { alloc::dealloc(memory, block.alloc_layout().unwrap()) };
+    /// ```
+    pub fn alloc_layout(&self) -> Option<Layout> {
+        self.alloc_layout
+    }
+
+    /// Returns the number of component slices.
+    ///
+    /// Slots of the block are in the range `0..num_fields()`.
+    pub fn num_fields(&self) -> usize {
+        self.slices.len()
+    }
+
+    /// Returns the distance in bytes between consecutive elements of a component.
+    ///
+    /// Equals the size of the component rounded up to its alignment.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    pub fn field_stride_bytes(&self, slot: LayoutSlot) -> usize {
+        self.slice(slot, "Block::field_stride_bytes").layout.size()
+    }
+
+    /// Returns the index of the last element, `None` if the block is empty.
+    pub fn last_index(&self) -> Option<usize> {
+        self.len.checked_sub(1)
+    }
+
+    /// Get the last element of a component slot.
+    ///
+    /// Returns `None` if the block is empty.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// The value of the element is undefined!
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn last<T: Copy>(&self, slot: LayoutSlot) -> Option<&mut T> {
+        self.slice_mut(slot, "Block::last").last_mut()
+    }
+
+    /// Get the raw pointer and len for a component slot.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    pub unsafe fn as_raw<T>(&self, slot: LayoutSlot) -> (*mut T, usize) {
+        let slice = self.typed_slice::<T>(slot, "Block::as_raw");
+        (slice.ptr.cast::<T>().as_ptr(), self.len)
+    }
+
+    /// Get the mutable slice for a component slot.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// All values in the resulting slice are undefined!
+    ///
+    /// The returned slice aliases the block memory. Retrieving the same slot
+    /// multiple times concurrently results in aliasing mutable references.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_slice<T: Copy>(&self, slot: LayoutSlot) -> &mut [T] {
+        self.slice_mut(slot, "Block::as_slice")
+    }
+
+    /// Get the mutable slice for a component slot reinterpreted as type `U`.
+    ///
+    /// Unlike `as_slice` the size and alignment of `U` are checked against the component.
+    ///
+    /// # Safety
+    ///
+    /// All bit patterns of the component **must** be valid values of `U`.
+    /// All values in the resulting slice are undefined!
+    ///
+    /// The returned slice aliases the block memory. Retrieving the same slot
+    /// multiple times concurrently results in aliasing mutable references.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    /// The size of `U` must match the size of the component and the alignment
+    /// of `U` must not exceed the alignment of the component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let color = layout.add_aligned::<[u8; 4]>(4);
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 4];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 16);
+    /// unsafe {
+    ///     block.as_slice::<[u8; 4]>(color).fill([0xFF; 4]);
+    ///     assert_eq!(block.as_slice_as::<u32>(color), [!0; 4]);
+    /// }
+    /// ```
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_slice_as<U: Copy>(&self, slot: LayoutSlot) -> &mut [U] {
+        let slice = self.slice(slot, "Block::as_slice_as");
+        assert_eq!(
+            mem::size_of::<U>(),
+            slice.layout.size(),
+            "Block::as_slice_as: size mismatch for layout slot {}",
+            slot
+        );
+        assert!(
+            mem::align_of::<U>() <= slice.layout.align(),
+            "Block::as_slice_as: alignment of layout slot {} is smaller than {}",
+            slot,
+            mem::align_of::<U>()
+        );
+        self.slice_mut(slot, "Block::as_slice_as")
+    }
+
+    /// Copy the elements of a component slot into a new vector.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// All elements of the component must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let id = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 4];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 16);
+    /// let ids = unsafe {
+    ///     block.as_slice::<u32>(id).copy_from_slice(&[4, 3, 2, 1]);
+    ///     block.field_to_vec::<u32>(id)
+    /// };
+    /// assert_eq!(ids, vec![4, 3, 2, 1]);
+    /// ```
+    pub unsafe fn field_to_vec<T: Copy>(&self, slot: LayoutSlot) -> Vec<T> {
+        self.slice_mut::<T>(slot, "Block::field_to_vec").to_vec()
+    }
+
+    /// Get the mutable slice of all values of an array component slot.
+    ///
+    /// Returns the values of all elements as one flat slice together with the number
+    /// of values per element. The values of element `i` are located at
+    /// `i * group..(i + 1) * group`.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add_array` for the passed slot.
+    /// All values in the resulting slice are undefined!
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    /// `T` must not be zero-sized and the size of the component must be a multiple of
+    /// the size of `T`.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_grouped_slice<T: Copy>(&self, slot: LayoutSlot) -> (&mut [T], usize) {
+        let slice = self.slice(slot, "Block::as_grouped_slice");
+        let size = mem::size_of::<T>();
+        assert!(
+            size != 0 && slice.layout.size().is_multiple_of(size),
+            "Block::as_grouped_slice: component size {} isn't a multiple of {}",
+            slice.layout.size(),
+            size
+        );
+
+        let group = slice.layout.size() / size;
+        let values = slice::from_raw_parts_mut(slice.ptr.cast::<T>().as_ptr(), self.len * group);
+        (values, group)
+    }
+
+    /// Get the mutable slice for a component slot split into chunks of `N` elements.
+    ///
+    /// Returns the full chunks and the remaining elements, which don't form a full chunk.
+    /// The chunks start at the beginning of the component slice, which is aligned to the
+    /// alignment of the component.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// All values in the resulting slices are undefined!
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    /// `N` must not be zero.
+    pub unsafe fn field_chunks_mut<T: Copy, const N: usize>(
+        &mut self,
+        slot: LayoutSlot,
+    ) -> (&mut [[T; N]], &mut [T]) {
+        assert_ne!(N, 0, "chunk size must not be zero");
+
+        let slice = self.slice_mut::<T>(slot, "Block::field_chunks_mut");
+        let num_chunks = slice.len() / N;
+        let (chunks, remainder) = slice.split_at_mut(num_chunks * N);
+        let chunks = slice::from_raw_parts_mut(chunks.as_mut_ptr() as *mut [T; N], num_chunks);
+        (chunks, remainder)
+    }
+
+    /// Get a single mutable slice spanning multiple component slots.
+    ///
+    /// The slices of the passed slots are concatenated in the given order into one slice
+    /// of `T`. Returns `None` if the slices aren't physically contiguous in that order,
+    /// if the combined size isn't a multiple of the size of `T` or the start isn't aligned
+    /// for `T`. The length of the resulting slice is the combined size divided by the size of `T`.
+    ///
+    /// # Safety
+    ///
+    /// The memory of all passed slots **must** be valid for `T`.
+    /// All values in the resulting slice are undefined!
+    ///
+    /// # Panics
+    ///
+    /// All slots must be valid values obtained by the corresponding block layout.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_combined_slice<T: Copy>(&self, slots: &[LayoutSlot]) -> Option<&mut [T]> {
+        let first = self.slice(*slots.first()?, "Block::as_combined_slice");
+
+        let mut end = first.ptr.as_ptr() as usize;
+        for slot in slots {
+            let slice = self.slice(*slot, "Block::as_combined_slice");
+            if slice.ptr.as_ptr() as usize != end {
+                return None;
+            }
+            end += slice.layout.size() * self.len;
+        }
+
+        let size = end - first.ptr.as_ptr() as usize;
+        let elem_size = mem::size_of::<T>();
+        if elem_size == 0
+            || !size.is_multiple_of(elem_size)
+            || !(first.ptr.as_ptr() as usize).is_multiple_of(mem::align_of::<T>())
+        {
+            return None;
+        }
+
+        Some(slice::from_raw_parts_mut(
+            first.ptr.cast::<T>().as_ptr(),
+            size / elem_size,
+        ))
+    }
+
+    /// Get the mutable slice for the first component with type `T`.
+    ///
+    /// Only components added via `add_typed` are considered. Returns `None`
+    /// if the block doesn't contain a component of the type.
+    ///
+    /// This requires a linear search over all components, prefer `as_slice`
+    /// with a stored slot for frequent accesses.
+    ///
+    /// # Safety
+    ///
+    /// All values in the resulting slice are undefined!
+    ///
+    /// The returned slice aliases the block memory. Retrieving the same slot
+    /// multiple times concurrently results in aliasing mutable references.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn slice_by_type<T: Copy + 'static>(&self) -> Option<&mut [T]> {
+        let slot = self
+            .slices
+            .iter()
+            .position(|slice| slice.type_id == Some(TypeId::of::<T>()))?;
+        Some(self.as_slice(slot))
+    }
+
+    /// Get the mutable slice for a component slot guarded by a borrow flag.
+    ///
+    /// Behaves like `as_slice`, but with `debug_assertions` enabled the block tracks
+    /// the borrow of the slot until the returned guard is dropped, similar to a `RefCell`.
+    /// Borrowing disjoint slots at the same time is allowed. In release builds
+    /// no tracking is performed.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// All values in the resulting slice are undefined!
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    /// With `debug_assertions` enabled, panics if the slot is already borrowed.
+    pub unsafe fn borrow_slice<T: Copy>(&self, slot: LayoutSlot) -> SliceRefMut<'_, T> {
+        let slice = self.slice_mut(slot, "Block::borrow_slice");
+
+        #[cfg(debug_assertions)]
+        {
+            let borrowed = self.borrows[slot].replace(true);
+            assert!(!borrowed, "slot {} is already borrowed", slot);
+        }
+
+        SliceRefMut {
+            slice,
+            #[cfg(debug_assertions)]
+            borrow: &self.borrows[slot],
+        }
+    }
+
+    /// Set all bytes of a component slice to zero.
+    ///
+    /// # Safety
+    ///
+    /// The all-zero bit pattern **must** be a valid value of the component type.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let force = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [1.0f32; 12];
+    /// let mut block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 48);
+    /// unsafe {
+    ///     block.zero_field(force);
+    ///     assert_eq!(block.as_slice::<[f32; 3]>(force), [[0.0; 3]; 4]);
+    /// }
+    /// ```
+    pub unsafe fn zero_field(&mut self, slot: LayoutSlot) {
+        let slice = self.slice(slot, "Block::zero_field");
+        ptr::write_bytes(slice.ptr.as_ptr(), 0, slice.layout.size() * self.len);
+    }
+
+    /// Set all bytes of multiple component slices to zero.
+    ///
+    /// Slices directly following each other in memory are cleared with a single write.
+    ///
+    /// # Safety
+    ///
+    /// The all-zero bit pattern **must** be a valid value of all component types.
+    ///
+    /// # Panics
+    ///
+    /// All slots must be valid values obtained by the corresponding block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let hits = layout.add::<u32>();
+    /// let misses = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [7u32; 8];
+    /// let mut block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 32);
+    /// unsafe {
+    ///     block.zero_fields(&[hits, misses]);
+    ///     assert_eq!(block.as_slice::<u32>(hits), [0; 4]);
+    ///     assert_eq!(block.as_slice::<u32>(misses), [0; 4]);
+    /// }
+    /// ```
+    pub unsafe fn zero_fields(&mut self, slots: &[LayoutSlot]) {
+        let mut spans = slots
+            .iter()
+            .map(|slot| {
+                let slice = self.slice(*slot, "Block::zero_fields");
+                (slice.offset, slice.ptr, slice.layout.size() * self.len)
+            })
+            .collect::<Vec<_>>();
+        spans.sort_by_key(|(offset, _, _)| *offset);
+
+        let mut run: Option<(usize, NonNull<u8>, usize)> = None;
+        for (offset, ptr, size) in spans {
+            run = match run {
+                Some((start, base, len)) if start + len == offset => {
+                    Some((start, base, len + size))
+                }
+                Some((start, base, len)) if start + len > offset => {
+                    // Repeated slot or zero-sized slice inside the current run.
+                    Some((start, base, len.max(offset + size - start)))
+                }
+                _ => {
+                    if let Some((_, base, len)) = run {
+                        ptr::write_bytes(base.as_ptr(), 0, len);
+                    }
+                    Some((offset, ptr, size))
+                }
+            };
+        }
+        if let Some((_, base, len)) = run {
+            ptr::write_bytes(base.as_ptr(), 0, len);
+        }
+    }
+
+    /// Write the raw bytes of a single component value at index `index`.
+    ///
+    /// Untyped counterpart to writing through `as_slice`, useful for scattering
+    /// an element from serialized or AoS data into the block field by field.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` **must** be a valid value of the component type.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout,
+    /// `index` must be smaller than the length of the block and the length of `bytes`
+    /// must match the size of the component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let id = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 4];
+    /// let mut block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 16);
+    /// unsafe {
+    ///     block.zero_field(id);
+    ///     block.write_element_bytes(2, id, &7u32.to_ne_bytes());
+    ///     assert_eq!(block.as_slice::<u32>(id), [0, 0, 7, 0]);
+    /// }
+    /// ```
+    pub unsafe fn write_element_bytes(&mut self, index: usize, slot: LayoutSlot, bytes: &[u8]) {
+        let slice = self.slice(slot, "Block::write_element_bytes");
+        let size = slice.layout.size();
+        assert!(
+            index < self.len,
+            "Block::write_element_bytes: index {} out of bounds: block has {} elements",
+            index,
+            self.len
+        );
+        assert_eq!(
+            bytes.len(),
+            size,
+            "Block::write_element_bytes: component size mismatch"
+        );
+        ptr::copy_nonoverlapping(bytes.as_ptr(), slice.ptr.as_ptr().add(index * size), size);
+    }
+
+    /// Drop the first `count` elements of all components added via `add_droppable`.
+    ///
+    /// Other components are left untouched.
+    ///
+    /// # Safety
+    ///
+    /// The first `count` elements of the droppable components **must** be initialized
+    /// and must not be used afterwards. Elements must be dropped exactly once,
+    /// usually right before freeing the memory of the block.
+    ///
+    /// # Panics
+    ///
+    /// `count` must not exceed the length of the block.
+    pub unsafe fn drop_elements(&mut self, count: usize) {
+        assert!(
+            count <= self.len,
+            "Block::drop_elements: count {} exceeds block length {}",
+            count,
+            self.len
+        );
+        for slice in &self.slices {
+            if let Some(drop) = slice.drop {
+                drop(slice.ptr.as_ptr(), count);
+            }
+        }
+    }
+
+    /// Fill the slices of all components with a default value.
+    ///
+    /// Components added via `add_with_default` are set to their default value
+    /// for all elements, other components are left untouched.
+    ///
+    /// # Safety
+    ///
+    /// The block **must** satisfy the validity requirements of [`Block`](struct.Block.html).
+    /// Previous values of the components are overwritten without being dropped.
+    pub unsafe fn init_defaults(&mut self) {
+        for slice in &self.slices {
+            if let Some(default) = slice.default.as_ref().filter(|d| !d.bytes.is_empty()) {
+                let bytes = &default.bytes;
+                let base = slice.ptr.as_ptr() as *mut MaybeUninit<u8>;
+                for i in 0..self.len {
+                    ptr::copy_nonoverlapping(
+                        bytes.as_ptr(),
+                        base.add(i * bytes.len()),
+                        bytes.len(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Iterate two components in lockstep.
+    ///
+    /// Calls `f` for every element with a mutable reference to the `dst` component
+    /// and a shared reference to the `src` component of the element.
+    ///
+    /// # Safety
+    ///
+    /// The types `A` and `B` **must** match the types used on `add` for the passed slots.
+    /// All elements of both components must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// Both slots must be valid values obtained by the corresponding block layout
+    /// and must refer to different components. The sizes of `A` and `B` must match
+    /// the sizes of the components.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 2]>();
+    /// let velocity = layout.add::<[f32; 2]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0f32; 16];
+    /// let mut block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 64);
+    ///
+    /// unsafe {
+    ///     block.as_slice::<[f32; 2]>(position).fill([1.0; 2]);
+    ///     block.as_slice::<[f32; 2]>(velocity).fill([1.0; 2]);
+    ///     block.for_each_pair(position, velocity, |p: &mut [f32; 2], v: &[f32; 2]| {
+    ///         p[0] += v[0];
+    ///         p[1] += v[1];
+    ///     });
+    ///     assert!(block.as_slice::<[f32; 2]>(position).iter().all(|p| *p == [2.0; 2]));
+    /// }
+    /// ```
+    pub unsafe fn for_each_pair<A, B, F>(&mut self, dst: LayoutSlot, src: LayoutSlot, mut f: F)
+    where
+        A: Copy,
+        B: Copy,
+        F: FnMut(&mut A, &B),
+    {
+        assert_ne!(dst, src, "components must be different");
+
+        let dst_slice = self.slice(dst, "Block::for_each_pair");
+        let src_slice = self.slice(src, "Block::for_each_pair");
+        assert_eq!(mem::size_of::<A>(), dst_slice.layout.size());
+        assert_eq!(mem::size_of::<B>(), src_slice.layout.size());
+
+        let dst = self.slice_mut::<A>(dst, "Block::for_each_pair");
+        let src = self.slice_mut::<B>(src, "Block::for_each_pair");
+        for (a, b) in dst.iter_mut().zip(src.iter()) {
+            f(a, b);
+        }
+    }
+
+    /// Create a writer for filling the block element by element.
+    ///
+    /// # Safety
+    ///
+    /// The block **must** satisfy the validity requirements of [`Block`](struct.Block.html)
+    /// while the writer is in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 2]>();
+    /// let mass = layout.add::<f32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u64; 32];
+    /// let mut block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 256);
+    ///
+    /// let mut writer = unsafe { block.writer() };
+    /// for (p, m) in vec![([0.0f32, 1.0], 2.0f32), ([3.0, 4.0], 5.0)] {
+    ///     writer.push(position, p).push(mass, m).commit();
+    /// }
+    /// assert_eq!(writer.len(), 2);
+    /// ```
+    pub unsafe fn writer(&mut self) -> FieldWriter<'_> {
+        FieldWriter {
+            cursors: vec![0; self.slices.len()],
+            len: 0,
+            block: self,
+        }
+    }
+
+    /// Recompute the block for a resized memory region.
+    ///
+    /// Places the slices as if `layout` had been applied again to the memory region
+    /// of this block, which now has a size of `new_size` bytes. The start of the memory
+    /// region stays the same, only the number of elements and the slice pointers change.
+    /// This allows reusing a block after growing or shrinking its memory in place.
+    ///
+    /// Blocks created via `apply_exact` keep using all bytes of the memory region.
+    /// A logical length set via `apply_with_len` is kept, clamped to the new capacity,
+    /// blocks using their full capacity follow the new capacity.
+    ///
+    /// The memory region is derived from `range()`, for blocks created via `apply_at`
+    /// it starts at the base pointer and `new_size` includes the offset.
+    ///
+    /// # Safety
+    ///
+    /// `layout` **must** be the layout this block has been created from.
+    /// The memory region must be valid for `new_size` bytes.
+    ///
+    /// No data is moved. Slices of all components except the first physical one
+    /// change their positions, the caller is responsible for moving the components
+    /// into their new slices, e.g. in reverse physical order when growing.
+    pub unsafe fn recompute(&mut self, layout: &BlockLayout, new_size: usize) {
+        assert_eq!(
+            layout.slot_map.len(),
+            self.slices.len(),
+            "layout doesn't match the block"
+        );
+
+        let data = match self.slices.first() {
+            Some(slice) => slice
+                .ptr
+                .as_ptr()
+                .wrapping_sub(slice.offset + self.range.start),
+            None => return,
+        };
+        let ptr = data as usize;
+        let base = ptr + self.range.start;
+        let end = if self.exact_end {
+            ptr + new_size
+        } else {
+            (ptr + new_size) & !(layout.layout.align() - 1)
+        };
+
+        let logical_len = Some(self.len).filter(|len| *len != self.capacity);
+        *self = Block {
+            exact_end: self.exact_end,
+            ..layout.apply_range(NonNull::new(data).unwrap(), base, end.max(base))
+        };
+        if let Some(len) = logical_len {
+            self.len = len.min(self.capacity);
+        }
+    }
+
+    /// Get the block of a nested layout.
+    ///
+    /// The returned block contains the components of the layout passed to `add_nested`
+    /// with the slots of the nested layout. It shares the memory and length of this block.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a slot obtained by `add_nested` of the corresponding block layout.
+    pub fn sub_block(&self, slot: LayoutSlot) -> Block {
+        let num_slots = self
+            .raw_slice(slot, "Block::sub_block")
+            .nested
+            .unwrap_or_else(|| panic!("Block::sub_block: layout slot {} isn't nested", slot));
+        let slots = (slot + 1..slot + 1 + num_slots).collect::<Vec<_>>();
+        self.split_fields(&slots)
+    }
+
+    /// Returns the byte spans of all component slices in physical order.
+    ///
+    /// Yields the slot, the byte offset of the slice relative to the start of the block
+    /// and the byte length of the slice for each component. Offsets in the memory region
+    /// are obtained by adding `range().start`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u8>();
+    /// let b = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 10];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 40);
+    /// assert_eq!(block.len(), 8);
+    ///
+    /// let spans = block.field_spans().collect::<Vec<_>>();
+    /// assert_eq!(spans, [(b, 0, 32), (a, 32, 8)]);
+    /// ```
+    pub fn field_spans(&self) -> impl Iterator<Item = (LayoutSlot, usize, usize)> + '_ {
+        let mut slots = (0..self.slices.len()).collect::<Vec<_>>();
+        slots.sort_by_key(|slot| (self.slices[*slot].offset, *slot));
+        slots.into_iter().map(move |slot| {
+            let slice = &self.slices[slot];
+            (slot, slice.offset, slice.layout.size() * self.len)
+        })
+    }
+
+    /// Split the block into `parts` blocks over disjoint element ranges.
+    ///
+    /// The elements are distributed evenly, the sizes of the blocks differ by at most one
+    /// element with the larger blocks first. Each block contains all components with the slices starting at
+    /// the first element of its range. The returned blocks report the range of this block.
+    ///
+    /// # Panics
+    ///
+    /// `parts` must not be zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let value = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 10];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 40);
+    ///
+    /// let chunks = block.chunks(3);
+    /// assert_eq!(chunks.iter().map(|c| c.len()).collect::<Vec<_>>(), [4, 3, 3]);
+    /// unsafe { chunks[1].as_slice::<u32>(value)[0] = 5 };
+    /// assert_eq!(data[4], 5);
+    /// ```
+    pub fn chunks(self, parts: usize) -> Vec<Block> {
+        assert_ne!(parts, 0, "number of parts must not be zero");
+
+        let chunk_len = self.len / parts;
+        let remainder = self.len % parts;
+        (0..parts)
+            .map(|i| {
+                let start = i * chunk_len + i.min(remainder);
+                let len = chunk_len + usize::from(i < remainder);
+                let slices = self
+                    .slices
+                    .iter()
+                    .map(|slice| {
+                        let offset = start * slice.layout.size();
+                        Slice {
+                            ptr: NonNull::new(slice.ptr.as_ptr().wrapping_add(offset)).unwrap(),
+                            offset: slice.offset + offset,
+                            ..slice.clone()
+                        }
+                    })
+                    .collect();
+                Block::new(self.range(), len, slices)
+            })
+            .collect()
+    }
+
+    /// Create a block over the elements `start..start + count` of this block.
+    ///
+    /// The returned block shares the memory of this block and contains all components
+    /// with the slices starting at element `start`. It reports the range of this block.
+    /// In contrast to `chunks` arbitrary windows can be selected, allowing to keep
+    /// multiple buffers of elements inside a single block.
+    ///
+    /// # Panics
+    ///
+    /// `start + count` must not exceed the length of the block.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let value = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 8];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 32);
+    /// let frames = [block.sub_region(0, 4), block.sub_region(4, 4)];
+    /// unsafe {
+    ///     frames[0].as_slice::<u32>(value).fill(0);
+    ///     frames[1].as_slice::<u32>(value).fill(1);
+    ///     assert_eq!(frames[0].as_slice::<u32>(value), [0; 4]);
+    /// }
+    /// assert_eq!(data, [0, 0, 0, 0, 1, 1, 1, 1]);
+    /// ```
+    pub fn sub_region(&self, start: usize, count: usize) -> Block {
+        assert!(
+            start.checked_add(count).is_some_and(|end| end <= self.len),
+            "Block::sub_region: elements {}..{} out of bounds for length {}",
+            start,
+            start.saturating_add(count),
+            self.len
+        );
+
+        let slices = self
+            .slices
+            .iter()
+            .map(|slice| {
+                let offset = start * slice.layout.size();
+                Slice {
+                    ptr: NonNull::new(slice.ptr.as_ptr().wrapping_add(offset)).unwrap(),
+                    offset: slice.offset + offset,
+                    ..slice.clone()
+                }
+            })
+            .collect();
+        Block::new(self.range(), count, slices)
+    }
+
+    /// Returns the offsets of all component slices in physical order.
+    ///
+    /// Contains one offset relative to the start of the block per slot, equal to the
+    /// offsets yielded by `field_spans`. Offsets in the memory region are obtained by
+    /// adding `range().start`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u8>();
+    /// let _ = layout.add::<u16>();
+    /// let _ = layout.add::<u64>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u64; 11];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 88);
+    /// assert_eq!(block.len(), 8);
+    /// assert_eq!(block.physical_offsets(), [0, 64, 80]);
+    /// ```
+    pub fn physical_offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Returns the base pointers of all component slices in physical order.
+    ///
+    /// Yields the slot, the pointer to the first element and the byte length of the
+    /// slice for each component, in the same order as `field_spans`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u8>();
+    /// let b = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 10];
+    /// let base = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+    /// let block = block_layout.apply(base, 40);
+    ///
+    /// let ptrs = block.field_ptrs().collect::<Vec<_>>();
+    /// assert_eq!(ptrs[0], (b, base, 32));
+    /// assert_eq!(ptrs[1].0, a);
+    /// ```
+    pub fn field_ptrs(&self) -> impl Iterator<Item = (LayoutSlot, NonNull<u8>, usize)> + '_ {
+        self.field_spans()
+            .map(move |(slot, _, len)| (slot, self.slices[slot].ptr, len))
+    }
+
+    /// Returns a descriptor of all component slices in physical order.
+    ///
+    /// Contains the pointer to the first element, the number of elements and the
+    /// size of a single element in bytes for each component, in the same order as
+    /// `field_spans`. Intended for passing blocks to foreign code expecting a table
+    /// of arrays.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let _ = layout.add::<u8>();
+    /// let _ = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 13];
+    /// let base = data.as_mut_ptr() as *mut u8;
+    /// let block = block_layout.apply(NonNull::new(base).unwrap(), 52);
+    /// assert_eq!(block.len(), 4);
+    /// assert_eq!(
+    ///     block.raw_parts(),
+    ///     [(base, 4, 12), (base.wrapping_add(48), 4, 1)]
+    /// );
+    /// ```
+    pub fn raw_parts(&self) -> Vec<(*mut u8, usize, usize)> {
+        self.field_ptrs()
+            .map(|(slot, ptr, _)| (ptr.as_ptr(), self.len, self.slices[slot].layout.size()))
+            .collect()
+    }
+
+    /// Create a block restricted to a subset of the component slots.
+    ///
+    /// The returned block shares the memory and length of this block, but only contains
+    /// the slices of the passed slots. Slot `slots[i]` of this block is accessible
+    /// as slot `i` in the returned block.
+    ///
+    /// Borrow tracking of `borrow_slice` isn't shared between both blocks.
+    ///
+    /// # Panics
+    ///
+    /// All slots must be valid values obtained by the corresponding block layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let position = layout.add::<[f32; 2]>();
+    /// let _velocity = layout.add::<[f32; 2]>();
+    /// let mass = layout.add::<f32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u64; 32];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 256);
+    ///
+    /// let view = block.split_fields(&[mass, position]);
+    /// assert_eq!(view.num_fields(), 2);
+    /// assert_eq!(view.len(), block.len());
+    /// unsafe {
+    ///     view.as_slice::<f32>(0)[0] = 4.0;
+    ///     assert_eq!(block.as_slice::<f32>(mass)[0], 4.0);
+    /// }
+    /// ```
+    pub fn split_fields(&self, slots: &[LayoutSlot]) -> Block {
+        let slices = slots
+            .iter()
+            .map(|slot| self.raw_slice(*slot, "Block::split_fields").clone())
+            .collect();
+        Block {
+            capacity: self.capacity,
+            ..Block::new(self.range(), self.len, slices)
+        }
+    }
+
+    /// Swap two elements across all component slices.
+    ///
+    /// # Safety
+    ///
+    /// The block **must** satisfy the validity requirements of [`Block`](struct.Block.html).
+    ///
+    /// # Panics
+    ///
+    /// `a` and `b` must be smaller than the number of elements in the block.
+    pub unsafe fn swap_elements(&mut self, a: usize, b: usize) {
+        assert!(a < self.len && b < self.len);
+        if a == b {
+            return;
+        }
+
+        for slice in &self.slices {
+            let size = slice.layout.size();
+            let base = slice.ptr.as_ptr() as *mut MaybeUninit<u8>;
+            ptr::swap_nonoverlapping(base.add(a * size), base.add(b * size), size);
+        }
+    }
+
+    /// Copy all elements of `other` into this block.
+    ///
+    /// Copies the first `min(self.len(), other.len())` elements of every component.
+    /// Both blocks must be derived from equal layouts, otherwise no data is copied
+    /// and the mismatch is reported.
+    ///
+    /// # Safety
+    ///
+    /// Both blocks **must** satisfy the validity requirements of [`Block`](struct.Block.html).
+    /// The blocks may overlap, no slices of either block may be in use during the copy.
+    pub unsafe fn copy_from(&mut self, other: &Block) -> Result<(), LayoutError> {
+        self.check_fields(other)?;
+
+        let len = self.len.min(other.len);
+        for (dst, src) in self.slices.iter().zip(&other.slices) {
+            ptr::copy(src.ptr.as_ptr(), dst.ptr.as_ptr(), src.layout.size() * len);
+        }
+
+        Ok(())
+    }
+
+    /// Gather the elements at `indices` of `other` into the first elements of this block.
+    ///
+    /// The element `indices[i]` of `other` will be copied to element `i` for all components.
+    /// Both blocks must be derived from equal layouts, otherwise no data is copied
+    /// and the mismatch is reported.
+    ///
+    /// # Panics
+    ///
+    /// The number of indices must not exceed the length of this block and all indices
+    /// must be smaller than the length of `other`.
+    ///
+    /// # Safety
+    ///
+    /// Both blocks **must** satisfy the validity requirements of [`Block`](struct.Block.html).
+    /// The blocks may overlap, no slices of either block may be in use during the copy.
+    pub unsafe fn gather_from(
+        &mut self,
+        other: &Block,
+        indices: &[usize],
+    ) -> Result<(), LayoutError> {
+        self.check_fields(other)?;
+
+        assert!(indices.len() <= self.len);
+        assert!(indices.iter().all(|&i| i < other.len));
+
+        for (dst, src) in self.slices.iter().zip(&other.slices) {
+            let size = src.layout.size();
+            for (i, &index) in indices.iter().enumerate() {
+                ptr::copy(
+                    src.ptr.as_ptr().add(index * size),
+                    dst.ptr.as_ptr().add(i * size),
+                    size,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fill the memory of all slices with the poison pattern.
+    ///
+    /// Only writes to the memory with the `poison` feature in builds with `debug_assertions`.
+    ///
+    /// # Safety
+    ///
+    /// The memory of all slices **must** be valid for writes.
+    pub(crate) unsafe fn poison(&self) {
+        #[cfg(all(feature = "poison", debug_assertions))]
+        for slice in &self.slices {
+            ptr::write_bytes(slice.ptr.as_ptr(), POISON, slice.layout.size() * self.len);
+        }
+    }
+
+    /// Check that the byte spans of all slices are pairwise disjoint and inside the block.
+    ///
+    /// Empty slices, e.g. of zero-sized components, can't overlap and are ignored.
+    fn slices_disjoint(&self) -> bool {
+        let size = self.range.end - self.range.start;
+        let mut end = 0;
+        self.field_spans()
+            .filter(|(_, _, len)| *len != 0)
+            .all(|(_, offset, len)| {
+                let disjoint = offset >= end;
+                end = offset + len;
+                disjoint && end <= size
+            })
+    }
+
+    /// Get the slice of a slot, panicking with a descriptive message for invalid slots.
+    #[track_caller]
+    fn slice(&self, slot: LayoutSlot, method: &str) -> &Slice {
+        self.raw_slice(slot, method).present(slot, method)
+    }
+
+    /// Get the slice of a slot, including absent slots.
+    #[track_caller]
+    fn raw_slice(&self, slot: LayoutSlot, method: &str) -> &Slice {
+        match self.slices.get(slot) {
+            Some(slice) => slice,
+            None => panic!(
+                "{}: invalid layout slot {}: block has {} fields",
+                method,
+                slot,
+                self.slices.len()
+            ),
+        }
+    }
+
+    /// Get the slice of a slot for accesses with type `T`.
+    #[track_caller]
+    fn typed_slice<T>(&self, slot: LayoutSlot, method: &str) -> &Slice {
+        self.slice(slot, method).typed::<T>(slot, method)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    unsafe fn slice_mut<T>(&self, slot: LayoutSlot, method: &str) -> &mut [T] {
+        let slice = self.typed_slice::<T>(slot, method);
+        slice::from_raw_parts_mut(slice.ptr.cast::<T>().as_ptr(), self.len)
+    }
+
+    /// Compare the component bytes of two blocks.
+    ///
+    /// Returns `true` if both blocks have the same number of elements, their components
+    /// have equal sizes and the bytes of all corresponding component slices are equal.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the components of both blocks **must** be initialized.
+    /// Padding bytes inside components take part in the comparison.
+    pub unsafe fn bytes_eq(&self, other: &Block) -> bool {
+        if self.len != other.len || self.check_fields(other).is_err() {
+            return false;
+        }
+
+        self.slices.iter().zip(&other.slices).all(|(a, b)| {
+            let size = a.layout.size() * self.len;
+            slice::from_raw_parts(a.ptr.as_ptr(), size)
+                == slice::from_raw_parts(b.ptr.as_ptr(), size)
+        })
+    }
+
+    /// Visit the bytes of all component slices in physical order.
+    ///
+    /// Calls `f` once per component with its slot and the contiguous bytes of its slice,
+    /// in the order of `field_spans`. Consecutive calls stream through the block memory
+    /// linearly, which is the cache-friendly order for passes over all elements and
+    /// components like serialization or hashing.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the components **must** be initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let a = layout.add::<u8>();
+    /// let b = layout.add::<u16>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u16; 6];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 12);
+    /// let mut out = Vec::new();
+    /// unsafe {
+    ///     block.as_slice::<u8>(a).fill(1);
+    ///     block.as_slice::<u16>(b).fill(0x0202);
+    ///     block.for_each_field_bytes(|slot, bytes| out.push((slot, bytes.to_vec())));
+    /// }
+    /// assert_eq!(out, [(b, vec![2; 8]), (a, vec![1; 4])]);
+    /// ```
+    pub unsafe fn for_each_field_bytes(&self, mut f: impl FnMut(LayoutSlot, &[u8])) {
+        for (slot, ptr, len) in self.field_ptrs() {
+            f(slot, slice::from_raw_parts(ptr.as_ptr(), len));
+        }
+    }
+
+    /// Compute a checksum over the component bytes of the block.
+    ///
+    /// Hashes the bytes of all component slices in physical order with the
+    /// 64-bit FNV-1a hash. The checksum is intended for detecting corruption,
+    /// it isn't suitable for cryptographic purposes.
+    ///
+    /// # Safety
+    ///
+    /// All bytes of the components **must** be initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// # use std::ptr::NonNull;
+    /// let mut layout = BlockLayout::build();
+    /// let value = layout.add::<u32>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let mut data = [0u32; 4];
+    /// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 16);
+    /// unsafe {
+    ///     block.as_slice::<u32>(value).fill(0);
+    ///     let checksum = block.checksum();
+    ///     block.as_slice::<u32>(value)[2] = 1;
+    ///     assert_ne!(block.checksum(), checksum);
+    /// }
+    /// ```
+    pub unsafe fn checksum(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET;
+        self.for_each_field_bytes(|_, bytes| {
+            hash = bytes.iter().fold(hash, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+            });
+        });
+        hash
+    }
+
+    fn check_fields(&self, other: &Block) -> Result<(), LayoutError> {
+        if self.slices.len() != other.slices.len() {
+            return Err(LayoutError::FieldCountMismatch {
+                expected: self.slices.len(),
+                found: other.slices.len(),
+            });
+        }
+
+        for (slot, (a, b)) in self.slices.iter().zip(&other.slices).enumerate() {
+            if a.layout.size() != b.layout.size() {
+                return Err(LayoutError::FieldSizeMismatch {
+                    slot,
+                    expected: a.layout.size(),
+                    found: b.layout.size(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy the element at index `src` over the element at index `dst` for all components.
+    fn copy_element(&mut self, src: usize, dst: usize) {
+        debug_assert!(src < self.len && dst < self.len);
+        if src == dst {
+            return;
+        }
+
+        for slice in &self.slices {
+            let size = slice.layout.size();
+            unsafe {
+                let base = slice.ptr.as_ptr();
+                ptr::copy_nonoverlapping(base.add(src * size), base.add(dst * size), size);
+            }
+        }
+    }
+}
+
+/// Bind the component slices of a block to variables.
+///
+/// Expands to a `let` binding for each listed component via `Block::as_slice`.
+/// Each entry has the form `name: Type => slot`.
+///
+/// The slice accesses are unsafe, the macro must be invoked inside an `unsafe` block.
+/// The caller is responsible for upholding the requirements of `as_slice`: the types
+/// must match the types used on `add` and each slot must only be listed once.
+///
+/// # Examples
+///
+/// ```rust
+/// # use norse_billow::{soa_fields, BlockLayout};
+/// # use std::ptr::NonNull;
+/// type Transform = [[f32; 4]; 4];
+/// type Velocity = [f32; 3];
+///
+/// let mut layout = BlockLayout::build();
+/// let transform_id = layout.add::<Transform>();
+/// let velocity_id = layout.add::<Velocity>();
+/// let block_layout = layout.finish();
+///
+/// let mut data = vec![0u64; 128];
+/// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 1024);
+///
+/// unsafe {
+///     soa_fields!(block, {
+///         transforms: Transform => transform_id,
+///         velocities: Velocity => velocity_id,
+///     });
+///     assert_eq!(transforms.len(), velocities.len());
+/// }
+/// ```
+///
+/// Invoking the macro outside of an `unsafe` block is rejected:
+///
+/// ```rust,compile_fail
+/// # use norse_billow::{soa_fields, BlockLayout};
+/// # use std::ptr::NonNull;
+/// let mut layout = BlockLayout::build();
+/// let id = layout.add::<u32>();
+/// let block_layout = layout.finish();
+///
+/// let mut data = [0u32; 4];
+/// let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 16);
+/// soa_fields!(block, { ids: f64 => id });
+/// ```
+#[macro_export]
+macro_rules! soa_fields {
+    ($block:expr, { $($name:ident : $ty:ty => $slot:expr),* $(,)? }) => {
+        let block: &$crate::Block = &$block;
+        $(
+            let $name = block.as_slice::<$ty>($slot);
+        )*
+    };
+}
+
+/// Writer scattering elements into the component slices of a block
+///
+/// Obtained from [`Block::writer`](struct.Block.html#method.writer).
+/// Each component of an element is written with `push`, afterwards the element is
+/// finalized with `commit`. Absent and nested components hold no data and are skipped.
+pub struct FieldWriter<'a> {
+    block: &'a mut Block,
+    cursors: Vec<usize>,
+    len: usize,
+}
+
+impl<'a> FieldWriter<'a> {
+    /// Write the component `slot` of the current element.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding block layout.
+    /// The size of `T` must match the size of the component and the alignment
+    /// of `T` must not exceed the alignment of the component.
+    /// Panics if the component has already been written for the current element
+    /// or the block is full.
+    pub fn push<T: Copy>(&mut self, slot: LayoutSlot, value: T) -> &mut Self {
+        let slice = self.block.slice(slot, "FieldWriter::push");
+        assert_eq!(mem::size_of::<T>(), slice.layout.size());
+        assert!(mem::align_of::<T>() <= slice.layout.align());
+        assert_eq!(
+            self.cursors[slot], self.len,
+            "slot {} already written for the current element",
+            slot
+        );
+        assert!(self.len < self.block.len, "block is full");
+
+        unsafe { slice.ptr.cast::<T>().as_ptr().add(self.len).write(value) };
+        self.cursors[slot] += 1;
+        self
+    }
+
+    /// Finalize the current element.
+    ///
+    /// # Panics
+    ///
+    /// All present components of the element must have been written.
+    pub fn commit(&mut self) {
+        for (slot, cursor) in self.cursors.iter().enumerate() {
+            let slice = &self.block.slices[slot];
+            if slice.absent || slice.nested.is_some() {
+                continue;
+            }
+            assert_eq!(
+                *cursor,
+                self.len + 1,
+                "slot {} not written for the current element",
+                slot
+            );
+        }
+        self.len += 1;
+    }
+
+    /// Returns the number of committed elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no elements have been committed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Guarded mutable slice of a block component
+///
+/// Obtained from [`Block::borrow_slice`](struct.Block.html#method.borrow_slice).
+pub struct SliceRefMut<'a, T> {
+    slice: &'a mut [T],
+    #[cfg(debug_assertions)]
+    borrow: &'a Cell<bool>,
+}
+
+impl<'a, T> Deref for SliceRefMut<'a, T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.slice
+    }
+}
+
+impl<'a, T> DerefMut for SliceRefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.slice
+    }
+}
+
+impl<'a, T> Drop for SliceRefMut<'a, T> {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        self.borrow.set(false);
+    }
+}
+
+/// Errors of fallible layout operations.
+///
+/// Shared by all fallible operations of the crate, errors of `std::alloc::Layout`
+/// are wrapped in `LayoutConstruction`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// Size computations overflowed.
+    Overflow,
+    /// The stored element layout doesn't match the component layouts.
+    Mismatch { expected: Layout, found: Layout },
+    /// Two blocks have a different number of fields.
+    FieldCountMismatch { expected: usize, found: usize },
+    /// The element size of a field differs between two blocks.
+    FieldSizeMismatch {
+        slot: LayoutSlot,
+        expected: usize,
+        found: usize,
+    },
+    /// The slot doesn't refer to a component of the layout.
+    InvalidSlot(LayoutSlot),
+    /// The component at this physical position isn't referenced by any slot.
+    Unreferenced(usize),
+    /// The alignment isn't a power of two or exceeds `MAX_ALIGNMENT`.
+    InvalidAlignment(usize),
+    /// The element stride isn't a multiple of the alignment.
+    InvalidStride { stride: usize, align: usize },
+    /// The element stride is smaller than the size of the packed components.
+    StrideTooSmall { stride: usize, required: usize },
+    /// The memory region is too small for the required number of bytes.
+    SizeTooSmall { required: usize, found: usize },
+    /// The memory region isn't aligned to the alignment of the layout.
+    UnalignedBuffer { align: usize },
+    /// The padding of the element stride exceeds the limit set via `warn_padding_over`.
+    ExcessivePadding { padding: usize, stride: usize },
+    /// Constructing a `Layout` failed.
+    LayoutConstruction(LayoutErr),
+}
+
+impl From<LayoutErr> for LayoutError {
+    fn from(err: LayoutErr) -> Self {
+        LayoutError::LayoutConstruction(err)
+    }
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayoutError::Overflow => write!(f, "layout size overflow"),
+            LayoutError::SizeTooSmall { required, found } => write!(
+                f,
+                "memory region too small: required {} bytes, found {}",
+                required, found
+            ),
+            LayoutError::LayoutConstruction(ref err) => write!(f, "invalid layout: {}", err),
+            LayoutError::Mismatch { expected, found } => write!(
+                f,
+                "element layout mismatch: expected {:?}, found {:?}",
+                expected, found
+            ),
+            LayoutError::FieldCountMismatch { expected, found } => write!(
+                f,
+                "block field count mismatch: expected {}, found {}",
+                expected, found
+            ),
+            LayoutError::FieldSizeMismatch {
+                slot,
+                expected,
+                found,
+            } => write!(
+                f,
+                "block field size mismatch for slot {}: expected {}, found {}",
+                slot, expected, found
+            ),
+            LayoutError::InvalidSlot(slot) => write!(f, "invalid layout slot {}", slot),
+            LayoutError::Unreferenced(physical) => {
+                write!(f, "component {} isn't referenced by any slot", physical)
+            }
+            LayoutError::InvalidAlignment(align) => write!(
+                f,
+                "invalid alignment {}: must be a power of two up to {}",
+                align, MAX_ALIGNMENT
+            ),
+            LayoutError::InvalidStride { stride, align } => write!(
+                f,
+                "stride {} isn't a multiple of the alignment {}",
+                stride, align
+            ),
+            LayoutError::StrideTooSmall { stride, required } => write!(
+                f,
+                "stride {} is smaller than the element size {}",
+                stride, required
+            ),
+            LayoutError::UnalignedBuffer { align } => {
+                write!(f, "memory region isn't aligned to {}", align)
+            }
+            LayoutError::ExcessivePadding { padding, stride } => write!(
+                f,
+                "{} padding bytes in an element stride of {} bytes",
+                padding, stride
+            ),
+        }
+    }
+}
+
+impl error::Error for LayoutError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            LayoutError::LayoutConstruction(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        let layout = BlockLayout::build().finish();
+        assert_eq!(layout.layout(), Layout::from_size_align(0, 1).unwrap());
+        assert_eq!(layout.size_for(0), 0);
+        assert_eq!(layout.size_for(100), 0);
+        assert_eq!(layout.capacity_for(0), 0);
+        assert_eq!(layout.capacity_for(32), 0);
+
+        let mut block = [0; 32];
+        let block = layout.apply(NonNull::new(block.as_mut_ptr()).unwrap(), 32);
+        assert_eq!(block.len(), 0);
+        assert_eq!(block.num_fields(), 0);
+        assert_eq!(block.range(), 0..0);
+    }
+
+    #[test]
+    fn recompute() {
+        let (layout, a, b) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let b = layout.add::<u16>();
+            (layout.finish(), a, b)
+        };
+
+        let mut data = [0u32; 33];
+        let ptr = unsafe { (data.as_mut_ptr() as *mut u8).add(1) };
+        let mut block = layout.apply(NonNull::new(ptr).unwrap(), 63);
+        let range = block.range();
+
+        unsafe {
+            block.recompute(&layout, 127);
+            let expected = layout.apply(NonNull::new(ptr).unwrap(), 127);
+            assert_eq!(block.range(), expected.range());
+            assert_eq!(block.len(), expected.len());
+            assert_eq!(block.as_raw::<u32>(a), expected.as_raw::<u32>(a));
+            assert_eq!(block.as_raw::<u16>(b), expected.as_raw::<u16>(b));
+
+            block.recompute(&layout, 63);
+            assert_eq!(block.range(), range);
+
+            let mut exact = layout.apply_exact(NonNull::new(ptr).unwrap(), 63);
+            exact.recompute(&layout, 129);
+            let expected_exact = layout.apply_exact(NonNull::new(ptr).unwrap(), 129);
+            assert_eq!(exact.len(), expected_exact.len());
+            assert!(exact.len() > layout.apply(NonNull::new(ptr).unwrap(), 129).len());
+
+            let mut logical = layout.apply_with_len(NonNull::new(ptr).unwrap(), 63, 3);
+            logical.recompute(&layout, 127);
+            assert_eq!(logical.len(), 3);
+            assert_eq!(logical.capacity(), expected.capacity());
+            logical.recompute(&layout, 16);
+            assert_eq!(logical.len(), logical.capacity());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Block::as_slice: layout slot 1 is absent")]
+    fn absent_slot() {
+        let (layout, a, b, c) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let b = layout.add_optional::<u64>(false);
+            let c = layout.add_optional::<u16>(true);
+            (layout.finish(), a, b, c)
+        };
+        assert_eq!(layout.layout(), Layout::from_size_align(6, 4).unwrap());
+
+        let mut data = [0u32; 8];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
+        assert_eq!(block.num_fields(), 3);
+        unsafe {
+            assert_eq!(block.as_slice::<u32>(a).len(), 5);
+            assert_eq!(block.as_slice::<u16>(c).len(), 5);
+            block.as_slice::<u64>(b);
+        }
+    }
+
+    #[test]
+    fn bytes_eq() {
+        let (layout, a) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let _ = layout.add::<u8>();
+            (layout.finish(), a)
+        };
+
+        let mut data_a = [0u32; 10];
+        let mut data_b = [0u32; 10];
+        let mut data_c = [0u32; 5];
+        let block_a = layout.apply(NonNull::new(data_a.as_mut_ptr() as _).unwrap(), 40);
+        let mut block_b = layout.apply(NonNull::new(data_b.as_mut_ptr() as _).unwrap(), 40);
+        let block_c = layout.apply(NonNull::new(data_c.as_mut_ptr() as _).unwrap(), 20);
+
+        unsafe {
+            assert!(block_a.bytes_eq(&block_b));
+            assert!(!block_a.bytes_eq(&block_c));
+
+            block_a.as_slice::<u32>(a)[3] = 7;
+            assert!(!block_a.bytes_eq(&block_b));
+            block_b.copy_from(&block_a).unwrap();
+            assert!(block_a.bytes_eq(&block_b));
+        }
+    }
+
+    #[test]
+    fn chunks_parallel() {
+        let (layout, a, b) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let b = layout.add::<u16>();
+            (layout.finish(), a, b)
+        };
+
+        let mut data = [0u32; 64];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 256);
+        let len = block.len();
+        let (ptr_a, _) = unsafe { block.as_raw::<u32>(a) };
+        let (ptr_b, _) = unsafe { block.as_raw::<u16>(b) };
+
+        std::thread::scope(|scope| {
+            for (i, chunk) in block.chunks(4).into_iter().enumerate() {
+                scope.spawn(move || unsafe {
+                    chunk.as_slice::<u32>(a).fill(i as u32);
+                    chunk.as_slice::<u16>(b).fill(i as u16);
+                });
+            }
+        });
+
+        let values_a = unsafe { slice::from_raw_parts(ptr_a, len) };
+        let values_b = unsafe { slice::from_raw_parts(ptr_b, len) };
+        let bounds = [0, 1, 2, 3].map(|c| c * (len / 4) + c.min(len % 4));
+        for i in 0..len {
+            let chunk = bounds.iter().rposition(|&start| start <= i).unwrap();
+            assert_eq!(values_a[i], chunk as u32);
+            assert_eq!(values_b[i], chunk as u16);
+        }
+    }
+
+    #[test]
+    fn oversized_alignment() {
+        let mut layout = BlockLayout::build();
+        let _ = layout.add::<u32>();
+        let _ = layout.add_raw(Layout::from_size_align(8, 1 << 40).unwrap());
+        assert_eq!(
+            layout.try_finish().err(),
+            Some(LayoutError::InvalidAlignment(1 << 40))
+        );
+
+        let mut layout = BlockLayout::build();
+        let _ = layout.add_raw(Layout::from_size_align(isize::MAX as usize / 2, 1).unwrap());
+        let _ = layout.add_raw(Layout::from_size_align(isize::MAX as usize / 2 + 2, 1).unwrap());
+        assert_eq!(layout.try_finish().err(), Some(LayoutError::Overflow));
+
+        let mut layout = BlockLayout::build();
+        let _ = layout.add_raw(Layout::from_size_align(4, MAX_ALIGNMENT).unwrap());
+        assert!(layout.try_finish().is_ok());
+
+        let mut layout = BlockLayout::build();
+        let _ = layout.add_raw(Layout::from_size_align(isize::MAX as usize / 2, 1).unwrap());
+        let _ = layout.add_raw(Layout::from_size_align(8, 1 << 40).unwrap());
+        let _ = layout.add_raw(Layout::from_size_align(isize::MAX as usize / 2, 1).unwrap());
+        assert_eq!(
+            layout.finish_checked().err(),
+            Some(vec![
+                LayoutError::InvalidAlignment(1 << 40),
+                LayoutError::Overflow
+            ])
+        );
+
+        let mut layout = BlockLayout::build();
+        let _ = layout.add::<u64>();
+        let _ = layout.add::<u8>();
+        let checked = layout.finish_checked().unwrap();
+        assert!(checked == BlockLayout::of::<(u64, u8)>().0);
+    }
+
+    #[test]
+    fn nested() {
+        let (inner, x, y) = {
+            let mut layout = BlockLayout::build();
+            let x = layout.add::<u16>();
+            let y = layout.add::<u32>();
+            (layout.finish(), x, y)
+        };
+        let (middle, a, b) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u8>();
+            let b = layout.add_nested(inner);
+            (layout.finish(), a, b)
+        };
+        let (layout, value, nested) = {
+            let mut layout = BlockLayout::build();
+            let value = layout.add::<u64>();
+            let nested = layout.add_nested(middle);
+            (layout.finish(), value, nested)
+        };
+        assert_eq!(layout.layout(), Layout::from_size_align(15, 8).unwrap());
+        assert!(!layout.is_present(nested));
+
+        let mut data = [0u64; 32];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 256);
+        let middle = block.sub_block(nested);
+        let inner = middle.sub_block(b);
+        assert_eq!(middle.num_fields(), 4);
+        assert_eq!(inner.num_fields(), 2);
+        assert_eq!(inner.len(), block.len());
+
+        unsafe {
+            block.as_slice::<u64>(value).fill(1);
+            middle.as_slice::<u8>(a).fill(2);
+            inner.as_slice::<u16>(x).fill(3);
+            inner.as_slice::<u32>(y).fill(4);
+
+            let len = block.len();
+            let spans = block.field_spans().collect::<Vec<_>>();
+            assert_eq!(
+                spans.iter().map(|(_, _, size)| size).sum::<usize>(),
+                15 * len
+            );
+            assert!(block.as_slice::<u64>(value).iter().all(|v| *v == 1));
+            assert!(middle.as_slice::<u8>(a).iter().all(|v| *v == 2));
+            assert!(inner.as_slice::<u16>(x).iter().all(|v| *v == 3));
+        }
+    }
+
+    #[test]
+    fn append_stable() {
+        let v1 = {
+            let mut layout = BlockLayout::build();
+            layout.extend(vec![Layout::new::<u8>(), Layout::new::<u16>()]);
+            layout.finish_append_stable()
+        };
+        let (v2, c, d) = {
+            let mut layout = BlockLayout::build();
+            layout.extend(vec![Layout::new::<u8>(), Layout::new::<u16>()]);
+            let c = layout.add::<u64>();
+            let d = layout.add::<[u8; 3]>();
+            (layout.finish_append_stable(), c, d)
+        };
+
+        let mut data = [0u64; 64];
+        let mut compared = 0;
+        for size in 0..512 {
+            let block_v1 = v1.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), size);
+            let block_v2 = v2.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), size);
+            assert!(v2.fits(8, size, block_v2.len()));
+            if !block_v2.is_empty() {
+                assert!(v2.size_for(block_v2.len()) <= size);
+            }
+
+            // All slices are inside the memory region and aligned.
+            let spans = block_v2.field_spans().collect::<Vec<_>>();
+            assert_eq!(
+                spans.iter().map(|(slot, _, _)| *slot).collect::<Vec<_>>(),
+                [0, 1, c, d]
+            );
+            for &(slot, offset, len) in &spans {
+                assert!(offset + len <= size);
+                assert_eq!(offset % v2.sub_layouts[v2.slot_map[&slot]].align(), 0);
+            }
+
+            if block_v1.len() == block_v2.len() {
+                let prefix = block_v1.field_spans().collect::<Vec<_>>();
+                assert_eq!(prefix[..], spans[..2]);
+                compared += 1;
+            }
+        }
+        assert!(compared > 0);
+    }
+
+    #[test]
+    fn size_for() {
+        let layout = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u64>();
+            let _ = layout.add::<[u8; 3]>();
+            layout.finish()
+        };
+
+        let max = layout.max_elements();
+        assert!(layout.size_for(max) <= isize::MAX as usize);
+        assert!(layout.block_layout(max + 1).is_err());
+
+        let mut data = [0u64; 32];
+        for n in 0..20 {
+            let size = layout.size_for(n);
+            assert!(layout.capacity_for(size) >= n);
+            assert!(layout.fits(1, layout.min_viable_buffer(n), n));
+            let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), size);
+            assert_eq!(block.len(), layout.capacity_for(size));
+        }
+    }
+
+    #[test]
+    fn single_zst() {
+        struct Foo;
+
+        let (layout, foo) = {
+            let mut layout = BlockLayout::build();
+            let foo = layout.add::<Foo>();
+            (layout.finish(), foo)
+        };
+
+        let mut data = [0; 32];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), 32);
+
+        unsafe {
+            block.as_raw::<Foo>(foo);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Block::as_slice: layout slot 1 holds a zero-sized component")]
+    fn zst_mismatch() {
+        struct Marker;
+
+        let (layout, value, marker) = {
+            let mut layout = BlockLayout::build();
+            let value = layout.add::<u32>();
+            let marker = layout.add::<Marker>();
+            (layout.finish(), value, marker)
+        };
+
+        let mut data = [0u32; 8];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
+        unsafe {
+            let (ptr, _) = block.as_raw::<Marker>(marker);
+            assert_eq!(ptr as usize % mem::align_of::<Marker>(), 0);
+            assert_eq!(block.as_slice::<u32>(value).len(), 8);
+            block.as_slice::<u32>(marker);
+        }
+    }
+
+    #[test]
+    fn ordering() {
+        #[derive(Copy, Clone)]
+        struct Small {
+            _a: u8,
+            _b: u8,
+            _c: u8,
+        }
+
+        #[derive(Copy, Clone)]
+        struct Large {
+            _a: f32,
+            _b: [u64; 8],
+        }
+
+        let (layout, small, large) = {
+            let mut layout = BlockLayout::build();
+            let small = layout.add::<Small>();
+            let large = layout.add::<Large>();
+            (layout.finish(), small, large)
+        };
+
+        let mut data = [0; 512];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), 512);
+
+        let small_layout = Layout::new::<Small>();
+        let large_layout = Layout::new::<Large>();
+        assert_eq!(
+            layout.layout().align(),
+            small_layout.align().max(large_layout.align())
+        );
+        assert_eq!(
+            layout.layout().size(),
+            small_layout.size() + large_layout.size()
+        );
+
+        unsafe {
+            block.as_slice::<Small>(small);
+            block.as_slice::<Large>(large);
+
+            // Large components are placed first, slots still refer to their own slices.
+            let (small_ptr, len) = block.as_raw::<Small>(small);
+            let (large_ptr, _) = block.as_raw::<Large>(large);
+            assert_eq!(small_ptr as usize, large_ptr.add(len) as usize);
+        }
+    }
+
+    #[test]
+    fn const_layout() {
+        const LAYOUTS: [Layout; 4] = [
+            Layout::new::<u8>(),
+            Layout::new::<u64>(),
+            Layout::new::<u16>(),
+            Layout::new::<u64>(),
+        ];
+        const NUM_ELEMENTS: usize = 5;
+        const SIZE: usize = BlockLayout::const_required_size(&LAYOUTS, NUM_ELEMENTS);
+
+        let mut builder = BlockLayout::build();
+        let slots = [
+            builder.add::<u8>(),
+            builder.add::<u64>(),
+            builder.add::<u16>(),
+            builder.add::<u64>(),
+        ];
+        let layout = builder.finish();
+        assert_eq!(layout.layout(), BlockLayout::const_layout(&LAYOUTS));
+
+        // Unaligned start to exercise the worst-case rounding.
+        let mut data = [0u64; SIZE / 8 + 2];
+        let ptr = unsafe { (data.as_mut_ptr() as *mut u8).add(1) };
+        let block = layout.apply(NonNull::new(ptr).unwrap(), SIZE);
+        assert!(block.len() >= NUM_ELEMENTS);
+
+        let size = (layout.layout().size() * NUM_ELEMENTS + 7) & !7;
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), size);
+        assert_eq!(block.len(), NUM_ELEMENTS);
+        for (i, slot) in slots.iter().enumerate() {
+            let (ptr, _) = unsafe { block.as_raw::<u8>(*slot) };
+            assert_eq!(
+                ptr as usize - data.as_ptr() as usize,
+                BlockLayout::const_offset(&LAYOUTS, i, NUM_ELEMENTS)
+            );
+        }
+    }
+
+    #[test]
+    fn copy_mismatch() {
+        let (layout_a, a) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            (layout.finish(), a)
+        };
+        let layout_b = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u32>();
+            let _ = layout.add::<u8>();
+            layout.finish()
+        };
+        let layout_c = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u16>();
+            layout.finish()
+        };
+
+        let mut data_a = [0u32; 8];
+        let mut data_b = [0u32; 8];
+        let mut data_c = [0u32; 8];
+        let mut block_a = layout_a.apply(NonNull::new(data_a.as_mut_ptr() as _).unwrap(), 32);
+        let block_b = layout_b.apply(NonNull::new(data_b.as_mut_ptr() as _).unwrap(), 32);
+        let block_c = layout_c.apply(NonNull::new(data_c.as_mut_ptr() as _).unwrap(), 32);
+
+        assert_eq!(
+            unsafe { block_a.copy_from(&block_b) },
+            Err(LayoutError::FieldCountMismatch {
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            unsafe { block_a.gather_from(&block_c, &[0]) },
+            Err(LayoutError::FieldSizeMismatch {
+                slot: 0,
+                expected: 4,
+                found: 2
+            })
+        );
+
+        let mut data_d = [0u32; 8];
+        let block_d = layout_a.apply(NonNull::new(data_d.as_mut_ptr() as _).unwrap(), 32);
+        unsafe {
+            block_d
+                .as_slice::<u32>(a)
+                .copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        }
+        unsafe { block_a.gather_from(&block_d, &[7, 0, 3]).unwrap() };
+        assert_eq!(unsafe { &block_a.as_slice::<u32>(a)[..3] }, [8, 1, 4]);
+        unsafe { block_a.copy_from(&block_d).unwrap() };
+        assert_eq!(
+            unsafe { block_a.as_slice::<u32>(a) },
+            [1, 2, 3, 4, 5, 6, 7, 8]
+        );
+    }
 
     #[test]
-    fn single_zst() {
-        struct Foo;
+    fn shared_zst() {
+        struct A;
+        #[repr(align(16))]
+        struct B;
 
-        let (layout, foo) = {
+        let (layout, value, a, b) = {
             let mut layout = BlockLayout::build();
-            let foo = layout.add::<Foo>();
-            (layout.finish(), foo)
+            let a = layout.add_shared_zst::<A>();
+            let value = layout.add::<u32>();
+            let b = layout.add_shared_zst::<B>();
+            (layout.finish(), value, a, b)
         };
+        assert_eq!(layout.sub_layouts.len(), 2);
+        assert_eq!(layout.layout().align(), 16);
 
-        let mut data = [0; 32];
-        let block = layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), 32);
+        let mut data = [0u8; 64];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), 64);
+        assert_eq!(block.slices.len(), 3);
 
         unsafe {
-            block.as_raw::<Foo>(foo);
+            let (a, _) = block.as_raw::<A>(a);
+            let (b, _) = block.as_raw::<B>(b);
+            assert_eq!(a as usize, b as usize);
+            assert_eq!(b as usize % 16, 0);
+            assert_eq!(block.as_slice::<u32>(value).len(), block.len());
         }
     }
 
     #[test]
-    fn ordering() {
-        #[derive(Copy, Clone)]
-        struct Small {
-            _a: u8,
-            _b: u8,
-            _c: u8,
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "already borrowed")]
+    fn borrow_twice() {
+        let (layout, a, b) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let b = layout.add::<u32>();
+            (layout.finish(), a, b)
+        };
+
+        let mut data = [0u32; 8];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
+
+        unsafe {
+            let mut slice_a = block.borrow_slice::<u32>(a);
+            let slice_b = block.borrow_slice::<u32>(b);
+            slice_a[0] = slice_b[0];
+            drop(slice_a);
+
+            let _slice_a = block.borrow_slice::<u32>(a);
+            let _slice_a2 = block.borrow_slice::<u32>(a);
         }
+    }
 
-        #[derive(Copy, Clone)]
-        struct Large {
-            _a: f32,
-            _b: [u64; 8],
+    #[test]
+    #[should_panic(expected = "not written")]
+    fn writer_out_of_sync() {
+        let (layout, a) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let _ = layout.add::<u16>();
+            (layout.finish(), a)
+        };
+
+        let mut data = [0u32; 8];
+        let mut block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
+        unsafe { block.writer() }.push(a, 1u32).commit();
+    }
+
+    #[test]
+    fn writer_absent() {
+        let (layout, a, b, c) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let _ = layout.add_optional::<u32>(false);
+            let mut inner = BlockLayout::build();
+            let _ = inner.add::<u8>();
+            let nested = layout.add_nested(inner.finish());
+            let c = layout.add::<u16>();
+            (layout.finish(), a, nested + 1, c)
+        };
+
+        let mut data = [0u32; 8];
+        let mut block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
+        let mut writer = unsafe { block.writer() };
+        writer.push(a, 1u32).push(b, 2u8).push(c, 3u16).commit();
+        writer.push(a, 4u32).push(b, 5u8).push(c, 6u16).commit();
+        assert_eq!(writer.len(), 2);
+        unsafe {
+            assert_eq!(block.as_slice::<u32>(a)[..2], [1, 4]);
+            assert_eq!(block.as_slice::<u8>(b)[..2], [2, 5]);
+            assert_eq!(block.as_slice::<u16>(c)[..2], [3, 6]);
         }
+    }
 
-        let (layout, small, large) = {
+    #[test]
+    fn chunks() {
+        let (layout, a) = {
             let mut layout = BlockLayout::build();
-            let small = layout.add::<Small>();
-            let large = layout.add::<Large>();
-            (layout.finish(), small, large)
+            let a = layout.add::<f32>();
+            (layout.finish(), a)
         };
 
-        let mut data = [0; 512];
-        let block = layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), 512);
+        let mut data = [0f32; 10];
+        let mut block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 40);
 
-        let small_layout = Layout::new::<Small>();
-        let large_layout = Layout::new::<Large>();
-        assert_eq!(
-            layout.layout().align(),
-            small_layout.align().max(large_layout.align())
-        );
+        let (chunks, remainder) = unsafe { block.field_chunks_mut::<f32, 4>(a) };
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(remainder.len(), 2);
+        chunks[1][3] = 1.0;
+        remainder[0] = 2.0;
+        assert_eq!(data[7..9], [1.0, 2.0]);
+    }
+
+    #[test]
+    fn over_aligned() {
+        let (layout, tiny, value) = {
+            let mut layout = BlockLayout::build();
+            let tiny = layout.add_aligned::<u8>(16);
+            let value = layout.add::<u32>();
+            (layout.finish(), tiny, value)
+        };
+        assert_eq!(layout.layout().size(), 20);
+        assert_eq!(layout.layout().align(), 16);
+
+        let mut data = [0u8; 256];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr()).unwrap(), 256);
+        assert!(block.len() >= 10);
+
+        unsafe {
+            assert_eq!(block.field_stride_bytes(tiny), 16);
+            assert_eq!(block.field_stride_bytes(value), 4);
+
+            let (tiny, len) = block.as_raw::<u8>(tiny);
+            let (value, _) = block.as_raw::<u32>(value);
+            for i in 0..len {
+                assert_eq!(tiny.add(16 * i) as usize % 16, 0);
+            }
+            assert_eq!(value as usize, tiny.add(16 * len) as usize);
+            assert_eq!(value as usize % 16, 0);
+        }
+    }
+
+    #[test]
+    fn builder_traits() {
+        let mut builder = LayoutBuilder::default();
+        let a = builder.add::<u16>();
+        builder.extend(vec![Layout::new::<u64>(), Layout::new::<u8>()]);
+        let layout = builder.finish();
+
+        assert_eq!(a, 0);
+        assert_eq!(layout.layout(), Layout::from_size_align(11, 8).unwrap());
+        assert_eq!(layout.permutation(), [1, 0, 2]);
+    }
+
+    #[test]
+    fn hash_eq() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash(layout: &BlockLayout) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            layout.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u32>();
+            let _ = layout.add::<[u8; 3]>();
+            layout.finish()
+        };
+        let b = {
+            let mut layout = BlockLayout::build();
+            layout.extend(vec![Layout::new::<u32>(), Layout::new::<[u8; 3]>()]);
+            layout.finish()
+        };
+        let c = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<[u8; 3]>();
+            let _ = layout.add::<u32>();
+            layout.finish()
+        };
+
+        assert!(a == b);
+        assert_eq!(hash(&a), hash(&b));
+        assert!(a != c);
+    }
+
+    #[test]
+    fn verify() {
+        let layout = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u32>();
+            let _ = layout.add::<u8>();
+            layout.finish()
+        };
+        assert_eq!(layout.verify(), Ok(()));
+
+        let corrupt = BlockLayout {
+            layout: Layout::from_size_align(4, 4).unwrap(),
+            ..layout
+        };
         assert_eq!(
-            layout.layout().size(),
-            small_layout.size() + large_layout.size()
+            corrupt.verify(),
+            Err(LayoutError::Mismatch {
+                expected: Layout::from_size_align(5, 4).unwrap(),
+                found: Layout::from_size_align(4, 4).unwrap(),
+            })
         );
 
+        let mut slot_map = IndexMap::new();
+        slot_map.insert(0, 0);
+        slot_map.insert(1, 0);
+        let corrupt = BlockLayout {
+            layout: Layout::from_size_align(5, 4).unwrap(),
+            slot_map,
+            ..corrupt
+        };
+        assert_eq!(corrupt.verify(), Err(LayoutError::Unreferenced(1)));
+    }
+
+    #[test]
+    fn fits() {
+        let layout = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u64>();
+            let _ = layout.add::<[u8; 3]>();
+            layout.finish()
+        };
+
+        let mut data = [0u64; 32];
+        for size in 8..200 {
+            let min_len = (0..8)
+                .map(|offset| {
+                    let ptr = unsafe { (data.as_mut_ptr() as *mut u8).add(offset) };
+                    layout.apply(NonNull::new(ptr).unwrap(), size).len()
+                })
+                .min()
+                .unwrap();
+            assert!(layout.fits(1, size, min_len));
+            assert!(!layout.fits(1, size, min_len + 1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Block::as_slice: invalid layout slot 2: block has 2 fields")]
+    fn invalid_slot() {
+        let layout = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u32>();
+            let _ = layout.add::<u16>();
+            layout.finish()
+        };
+
+        let mut data = [0u32; 8];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
         unsafe {
-            block.as_slice::<Small>(small);
-            block.as_slice::<Large>(large);
+            block.as_slice::<u32>(2);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "poison", debug_assertions))]
+    fn poison() {
+        let (layout, a, b) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let b = layout.add::<u8>();
+            (layout.finish(), a, b)
+        };
+
+        let mut data = [0u32; 8];
+        let len = layout
+            .apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32)
+            .len();
+        assert_eq!(data, [0; 8]);
+        let block =
+            unsafe { layout.apply_poisoned(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32) };
+        assert_eq!(block.len(), len);
+        unsafe {
+            assert!(block.as_slice::<u32>(a).iter().all(|v| *v == 0xCDCD_CDCD));
+            assert!(block.as_slice::<u8>(b).iter().all(|v| *v == POISON));
+        }
+    }
+
+    #[test]
+    fn combined_slice() {
+        let (layout, a, b, c) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<[f32; 3]>();
+            let b = layout.add::<u64>();
+            let c = layout.add::<[f32; 3]>();
+            (layout.finish(), a, b, c)
+        };
+
+        let mut data = [0u64; 40];
+        let block = layout.apply(NonNull::new(data.as_mut_ptr() as _).unwrap(), 320);
+        let len = block.len();
+
+        unsafe {
+            let combined = block.as_combined_slice::<f32>(&[a, c]).unwrap();
+            assert_eq!(combined.len(), 6 * len);
+            combined[3 * len] = 1.0;
+            assert_eq!(block.as_slice::<[f32; 3]>(c)[0][0], 1.0);
+
+            assert!(block.as_combined_slice::<f32>(&[c, a]).is_none());
+            assert!(block.as_combined_slice::<f32>(&[b, c]).is_none());
+        }
+    }
+
+    #[test]
+    fn apply_empty_region() {
+        let mut layout = BlockLayout::build();
+        let a = layout.add::<u32>();
+        let b = layout.add::<u8>();
+        let block_layout = layout.finish();
+
+        let mut data = [0u32; 4];
+        let base = data.as_mut_ptr() as *mut u8;
+        for (offset, size) in [(0, 0), (1, 0), (1, 2)] {
+            let ptr = NonNull::new(unsafe { base.add(offset) }).unwrap();
+            let block = block_layout.apply(ptr, size);
+            assert_eq!(block.len(), 0);
+            assert!(block.range().is_empty());
+            unsafe {
+                assert!(block.as_slice::<u32>(a).is_empty());
+                assert!(block.as_slice::<u8>(b).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn repack_without() {
+        let mut layout = BlockLayout::build();
+        let a = layout.add::<u16>();
+        let b = layout.add::<u64>();
+        let c = layout.add::<u32>();
+        let block_layout = layout.finish();
+
+        let block = block_layout.apply_boxed(vec![0u8; 256].into_boxed_slice());
+        unsafe {
+            for i in 0..block.len() {
+                block.as_slice::<u16>(a)[i] = i as u16;
+                block.as_slice::<u64>(b)[i] = !0;
+                block.as_slice::<u32>(c)[i] = 3 * i as u32;
+            }
+        }
+
+        let (shrunk, remap) = block_layout.without(b);
+        assert_eq!(shrunk.layout().size(), 6);
+        assert_eq!(remap.get(b), None);
+
+        let repacked = block.repack_without(&block_layout, b);
+        assert_eq!(repacked.num_fields(), 2);
+        assert!(repacked.len() >= block.len());
+        unsafe {
+            let len = block.len();
+            assert_eq!(
+                &repacked.as_slice::<u16>(remap.get(a).unwrap())[..len],
+                block.as_slice::<u16>(a)
+            );
+            assert_eq!(
+                &repacked.as_slice::<u32>(remap.get(c).unwrap())[..len],
+                block.as_slice::<u32>(c)
+            );
+        }
+    }
+
+    #[test]
+    fn aligning_field() {
+        let mut layout = BlockLayout::build();
+        let a = layout.add::<u64>();
+        let b = layout.add::<[u8; 3]>();
+        let c = layout.add::<u16>();
+        let block_layout = layout.finish();
+
+        let mut data = [0u64; 128];
+        let base = data.as_mut_ptr() as *mut u8;
+        for offset in 0..16 {
+            for &slot in &[a, b, c] {
+                let ptr = NonNull::new(unsafe { base.add(offset) }).unwrap();
+                let block = block_layout
+                    .apply_aligning_field(ptr, 1000, slot, 128)
+                    .unwrap();
+                assert!(!block.is_empty());
+                assert!(block.range().end <= 1000);
+                let (field, _) = unsafe { block.as_raw::<u8>(slot) };
+                assert_eq!(field as usize % 128, 0);
+            }
+        }
+    }
+
+    // Exercised by `cargo miri test`, slice pointers must carry the provenance of the region.
+    #[test]
+    fn provenance() {
+        let mut layout = BlockLayout::build();
+        let a = layout.add::<u16>();
+        let b = layout.add::<u64>();
+        let block_layout = layout.finish();
+
+        let mut data = vec![0u64; 16];
+        let ptr = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+        let block = block_layout.apply(ptr, 128);
+        unsafe {
+            block.as_slice::<u16>(a).fill(1);
+            block.as_slice::<u64>(b).fill(2);
+            assert!(block.as_slice::<u16>(a).iter().all(|v| *v == 1));
+            assert!(block.as_slice::<u64>(b).iter().all(|v| *v == 2));
+        }
+    }
+
+    // Exercised by `cargo miri test`, the aligned start must be derived from the region pointer.
+    #[test]
+    fn provenance_unaligned() {
+        let mut layout = BlockLayout::build();
+        let a = layout.add::<u32>();
+        let block_layout = layout.finish();
+
+        let mut data = vec![0u32; 9];
+        let base = data.as_mut_ptr() as *mut u8;
+        let ptr = NonNull::new(unsafe { base.add(1) }).unwrap();
+        let block = block_layout.apply(ptr, 35);
+        assert_eq!(block.range(), 3..35);
+        unsafe {
+            block.as_slice::<u32>(a).fill(7);
+            assert_eq!(block.as_slice::<u32>(a), [7; 8]);
+        }
+        assert_eq!(&data[1..], [7; 8]);
+    }
+
+    #[test]
+    fn remove_tie_break() {
+        let mut layout = BlockLayout::build();
+        let a = layout.add::<u32>();
+        let b = layout.add::<u32>();
+        let c = layout.add::<u32>();
+        let d = layout.add::<u64>();
+        layout.remove(b);
+        let block_layout = layout.finish();
+
+        // Survivors of equal alignment keep their declaration order.
+        let physical = block_layout.inverse_permutation();
+        assert!(physical[d] < physical[a]);
+        assert!(physical[a] < physical[c]);
+        assert!(!block_layout.is_present(b));
+        assert_eq!(block_layout.layout().size(), 16);
+
+        let mut data = [0u64; 8];
+        let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 64);
+        assert_eq!(block.len(), 4);
+        let spans = block
+            .field_spans()
+            .filter(|(_, _, len)| *len != 0)
+            .collect::<Vec<_>>();
+        assert_eq!(spans, [(d, 0, 32), (a, 32, 16), (c, 48, 16)]);
+    }
+
+    #[test]
+    fn drop_elements() {
+        use std::rc::Rc;
+
+        let mut layout = BlockLayout::build();
+        let shared = layout.add_droppable::<Rc<u32>>();
+        let plain = layout.add_droppable::<u32>();
+        let block_layout = layout.finish();
+
+        let value = Rc::new(0);
+        let mut block = block_layout.apply_boxed(vec![0u8; 256].into_boxed_slice());
+        unsafe {
+            let (values, len) = block.as_raw::<Rc<u32>>(shared);
+            for i in 0..len {
+                values.add(i).write(value.clone());
+            }
+            block.as_slice::<u32>(plain).fill(1);
+            assert_eq!(Rc::strong_count(&value), len + 1);
+
+            block.drop_elements(len);
+        }
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn apply_into() {
+        let mixed = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u8>();
+            let _ = layout.add_shared_zst::<()>();
+            let _ = layout.add_shared_zst::<[u32; 0]>();
+            let _ = layout.add::<u64>();
+            let _ = layout.add::<[u16; 3]>();
+            layout.finish()
+        };
+        let zero_sized = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<()>();
+            let _ = layout.add_aligned::<[u8; 0]>(8);
+            layout.finish()
+        };
+        let empty = BlockLayout::build().finish();
+
+        let mut data = [0u64; 64];
+        let base = data.as_mut_ptr() as *mut u8;
+        for block_layout in [&mixed, &zero_sized, &empty] {
+            let mut block = block_layout.apply(NonNull::new(base).unwrap(), 0);
+            for offset in 0..8 {
+                for size in (0..500).step_by(7) {
+                    let ptr = NonNull::new(unsafe { base.add(offset) }).unwrap();
+                    let expected = block_layout.apply(ptr, size);
+                    block_layout.apply_into(&mut block, ptr, size);
+
+                    assert_eq!(block.range(), expected.range());
+                    assert_eq!(block.len(), expected.len());
+                    assert_eq!(block.physical_offsets(), expected.physical_offsets());
+                    assert_eq!(
+                        block.field_ptrs().collect::<Vec<_>>(),
+                        expected.field_ptrs().collect::<Vec<_>>()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn apply_into_other_layout() {
+        use std::rc::Rc;
+
+        let droppable = {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add_droppable::<Rc<u32>>();
+            layout.finish()
+        };
+        let (plain, (value,)) = BlockLayout::of::<(usize,)>();
+
+        let mut data = [0usize; 16];
+        let base = NonNull::new(data.as_mut_ptr() as *mut u8).unwrap();
+        let mut block = droppable.apply(base, 128);
+        plain.apply_into(&mut block, base, 128);
+        assert_eq!(block.slices[value].type_id, plain.types[value]);
+        assert!(block.slices[value].drop.is_none());
+
+        // Running the `Rc` drop glue on plain integers would be undefined behavior.
+        unsafe {
+            block.as_slice::<usize>(value).fill(1);
+            block.drop_elements(block.len());
+        }
+        assert_eq!(data, [1; 16]);
+    }
+
+    #[test]
+    fn apply_fixed() {
+        let mut layout = BlockLayout::build();
+        let _ = layout.add::<u8>();
+        let _ = layout.add_shared_zst::<()>();
+        let _ = layout.add::<u64>();
+        let _ = layout.add::<[u16; 3]>();
+        let block_layout = layout.finish();
+
+        let mut data = [0u64; 64];
+        let base = data.as_mut_ptr() as *mut u8;
+        for offset in 0..8 {
+            for size in (0..500).step_by(7) {
+                let ptr = NonNull::new(unsafe { base.add(offset) }).unwrap();
+                let expected = block_layout.apply(ptr, size);
+                let block = block_layout.apply_fixed::<4>(ptr, size);
+
+                assert_eq!(block.range(), expected.range());
+                assert_eq!(block.len(), expected.len());
+                for (fixed, slice) in block.slices.iter().zip(&expected.slices) {
+                    assert_eq!(fixed.ptr, slice.ptr);
+                    assert_eq!(fixed.layout, slice.layout);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Block::as_slice: layout slot 0 is absent")]
+    fn apply_prefix_empty() {
+        let (layout, (a, _)) = BlockLayout::of::<(u32, u16)>();
+        let mut data = [0u32; 8];
+        let ptr = NonNull::new(data.as_mut_ptr() as _).unwrap();
+        let block = layout.apply_prefix(ptr, 32, 0);
+        assert_eq!(block.len(), layout.apply(ptr, 32).len());
+        assert_eq!(block.num_fields(), 2);
+        unsafe {
+            block.as_slice::<u32>(a);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedBlock::as_slice: layout slot 1 is absent")]
+    fn fixed_absent_slot() {
+        let (layout, a, b) = {
+            let mut layout = BlockLayout::build();
+            let a = layout.add::<u32>();
+            let b = layout.add_optional::<u64>(false);
+            (layout.finish(), a, b)
+        };
+
+        let mut data = [0u32; 8];
+        let block = layout.apply_fixed::<2>(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
+        unsafe {
+            assert_eq!(block.as_slice::<u32>(a).len(), 8);
+            block.as_slice::<u64>(b);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedBlock::as_raw: layout slot 1 holds a zero-sized component")]
+    fn fixed_zero_sized() {
+        let (layout, _) = BlockLayout::of::<(u32, ())>();
+        let mut data = [0u32; 8];
+        let block = layout.apply_fixed::<2>(NonNull::new(data.as_mut_ptr() as _).unwrap(), 32);
+        unsafe {
+            block.as_raw::<u32>(1);
+        }
+    }
+
+    #[test]
+    fn disjoint_slices() {
+        // Linear congruential generator, deterministic across runs.
+        let mut state = 0x853c_49e6_748f_ea9bu64;
+        let mut next = |bound: usize| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as usize % bound
+        };
+
+        let mut data = vec![0u64; 512];
+        let base = data.as_mut_ptr() as *mut u8;
+        for _ in 0..500 {
+            let mut layout = BlockLayout::build();
+            let num_fields = next(8);
+            for _ in 0..num_fields {
+                let align = 1 << next(7);
+                let size = align * next(4) + next(align);
+                let _ = layout.add_raw(Layout::from_size_align(size, align).unwrap());
+            }
+            let block_layout = if next(2) == 0 {
+                layout.finish()
+            } else {
+                layout.finish_append_stable()
+            };
+
+            let offset = next(64);
+            let size = next(4096 - offset);
+            let ptr = NonNull::new(unsafe { base.add(offset) }).unwrap();
+            let block = block_layout.apply(ptr, size);
+
+            let mut spans = (0..num_fields)
+                .map(|slot| {
+                    let slice = &block.slices[slot];
+                    assert_eq!(slice.ptr.as_ptr() as usize % slice.layout.align(), 0);
+                    let start = slice.ptr.as_ptr() as usize - base as usize;
+                    (start, start + slice.layout.size() * block.len)
+                })
+                .filter(|(start, end)| start != end)
+                .collect::<Vec<_>>();
+            spans.sort_unstable();
+            for pair in spans.windows(2) {
+                assert!(pair[0].1 <= pair[1].0, "overlapping slices {:?}", pair);
+            }
+            if let (Some(first), Some(last)) = (spans.first(), spans.last()) {
+                assert!(first.0 >= offset && last.1 <= offset + size);
+            }
+        }
+    }
+
+    #[test]
+    fn const_layout_padding() {
+        let layouts = [
+            Layout::from_size_align(1, 4).unwrap(),
+            Layout::from_size_align(3, 2).unwrap(),
+            Layout::from_size_align(9, 8).unwrap(),
+        ];
+        let mut layout = BlockLayout::build();
+        let slots = layouts
+            .iter()
+            .map(|component| layout.add_raw(*component))
+            .collect::<Vec<_>>();
+        let block_layout = layout.finish();
+        assert_eq!(block_layout.layout().size(), 24);
+        assert_eq!(BlockLayout::const_layout(&layouts), block_layout.layout());
+
+        let mut data = [0u64; 64];
+        let block = block_layout.apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 512);
+        let spans = block.field_spans().collect::<Vec<_>>();
+        for (i, slot) in slots.into_iter().enumerate() {
+            let (_, offset, _) = spans.iter().find(|(s, _, _)| *s == slot).unwrap();
+            assert_eq!(BlockLayout::const_offset(&layouts, i, block.len()), *offset);
+        }
+    }
+
+    #[test]
+    fn eq_builder_order() {
+        let build = |removed: [LayoutSlot; 2]| {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add::<u32>();
+            let _ = layout.add::<u8>();
+            let _ = layout.add::<u16>();
+            for slot in removed {
+                layout.remove(slot);
+            }
+            layout.finish()
+        };
+        let x = build([0, 2]);
+        let y = build([2, 0]);
+        assert!(x.structurally_equivalent(&y));
+        assert!(x == y);
+
+        let droppable = |drop: bool| {
+            let mut layout = BlockLayout::build();
+            if drop {
+                let _ = layout.add_droppable::<String>();
+            } else {
+                let _ = layout.add::<String>();
+            }
+            layout.finish()
+        };
+        assert!(droppable(true) == droppable(true));
+        assert!(droppable(true) != droppable(false));
+
+        let default = || {
+            let mut layout = BlockLayout::build();
+            let _ = layout.add_with_default(7u32);
+            layout.finish()
+        };
+        let (plain, _) = BlockLayout::of::<(u32,)>();
+        let a = default();
+        assert!(a == a);
+        assert!(a == default());
+        assert!(a != plain);
+
+        let mut layout = BlockLayout::build();
+        let _ = layout.add_with_default(8u32);
+        assert!(a != layout.finish());
+    }
+
+    #[test]
+    fn chunks_distribution() {
+        let (layout, _) = BlockLayout::of::<(u16,)>();
+        let mut data = [0u16; 8];
+        let base = data.as_mut_ptr() as *mut u8;
+        for len in 0..8 {
+            for parts in 1..10 {
+                let block = layout.apply_with_len(NonNull::new(base).unwrap(), 16, len);
+                let chunks = block.chunks(parts);
+                let lens = chunks.iter().map(|c| c.len()).collect::<Vec<_>>();
+                assert_eq!(lens.iter().sum::<usize>(), len);
+                assert!(lens.windows(2).all(|w| w[0] >= w[1] && w[0] - w[1] <= 1));
+
+                let mut expected = base;
+                for chunk in &chunks {
+                    let (ptr, len) = unsafe { chunk.as_raw::<u16>(0) };
+                    assert_eq!(ptr as *mut u8, expected);
+                    expected = expected.wrapping_add(2 * len);
+                }
+            }
         }
     }
 }
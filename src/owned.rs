@@ -0,0 +1,109 @@
+use crate::{Block, BlockLayout, LayoutSlot};
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::ptr::NonNull;
+
+impl BlockLayout {
+    /// Apply the block layout to a boxed memory region, taking ownership of the memory.
+    ///
+    /// The returned block frees the memory on drop.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let velocity = layout.add::<[f32; 3]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let block = block_layout.apply_boxed(vec![0u8; 256].into_boxed_slice());
+    /// assert!(block.len() >= 20);
+    /// unsafe { block.as_slice::<[f32; 3]>(velocity)[0] = [1.0; 3] };
+    /// ```
+    #[must_use]
+    pub fn apply_boxed(&self, buf: Box<[u8]>) -> OwnedBlock {
+        let size = buf.len();
+        let memory = NonNull::from(Box::leak(buf));
+        // The block owns the leaked buffer.
+        let block = unsafe { self.apply_poisoned(memory.cast(), size) };
+
+        OwnedBlock { block, memory }
+    }
+}
+
+impl Block {
+    /// Copy the block into a new allocation without the component of `slot`.
+    ///
+    /// `layout` must be the layout this block has been applied with. The returned
+    /// block is laid out according to `layout.without(slot)` and holds at least `len()`
+    /// elements, the first `len()` elements of all remaining components are copied.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::BlockLayout;
+    /// let mut layout = BlockLayout::build();
+    /// let id = layout.add::<u32>();
+    /// let scratch = layout.add::<[u8; 64]>();
+    /// let block_layout = layout.finish();
+    ///
+    /// let block = block_layout.apply_boxed(vec![0u8; 1024].into_boxed_slice());
+    /// unsafe { block.as_slice::<u32>(id)[0] = 7 };
+    ///
+    /// let (_, remap) = block_layout.without(scratch);
+    /// let repacked = block.repack_without(&block_layout, scratch);
+    /// assert_eq!(unsafe { repacked.as_slice::<u32>(remap.get(id).unwrap())[0] }, 7);
+    /// ```
+    pub fn repack_without(&self, layout: &BlockLayout, slot: LayoutSlot) -> OwnedBlock {
+        let (target, remap) = layout.without(slot);
+        let size = target.min_viable_buffer(self.len);
+        let block = target.apply_boxed(vec![0; size].into_boxed_slice());
+        debug_assert!(block.len >= self.len);
+
+        for (old_slot, src) in self.slices.iter().enumerate() {
+            if let Some(new_slot) = remap.get(old_slot) {
+                let dst = &block.slices[new_slot];
+                unsafe {
+                    ptr::copy_nonoverlapping(
+                        src.ptr.as_ptr(),
+                        dst.ptr.as_ptr(),
+                        src.layout.size() * self.len,
+                    );
+                }
+            }
+        }
+
+        block
+    }
+}
+
+/// Memory block owning its boxed memory region
+///
+/// Obtained from [`BlockLayout::apply_boxed`](struct.BlockLayout.html#method.apply_boxed).
+pub struct OwnedBlock {
+    block: Block,
+    memory: NonNull<[u8]>,
+}
+
+impl Deref for OwnedBlock {
+    type Target = Block;
+    fn deref(&self) -> &Block {
+        &self.block
+    }
+}
+
+impl DerefMut for OwnedBlock {
+    fn deref_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+}
+
+impl Drop for OwnedBlock {
+    fn drop(&mut self) {
+        drop(unsafe { Box::from_raw(self.memory.as_ptr()) });
+    }
+}
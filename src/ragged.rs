@@ -0,0 +1,294 @@
+use crate::{align_offset, LayoutError, LayoutSlot, MAX_ALIGNMENT};
+use std::alloc::Layout;
+use std::cmp::Reverse;
+use std::mem;
+use std::ops::Range;
+use std::ptr::NonNull;
+use std::slice;
+
+/// Ragged layout builder
+///
+/// Each component declares its own number of elements.
+pub struct RaggedLayoutBuilder {
+    fields: Vec<(Layout, usize)>,
+}
+
+impl RaggedLayoutBuilder {
+    /// Add a new typed component with `count` elements to the layout.
+    #[must_use]
+    pub fn add<T>(&mut self, count: usize) -> LayoutSlot {
+        self.add_raw(Layout::new::<T>(), count)
+    }
+
+    /// Add a new untyped component with `count` elements to the layout.
+    ///
+    /// The size of the layout is rounded up to its alignment to obtain the element stride.
+    #[must_use]
+    pub fn add_raw(&mut self, layout: Layout, count: usize) -> LayoutSlot {
+        let slot = self.fields.len();
+        self.fields.push((layout.pad_to_align(), count));
+        slot
+    }
+
+    /// Bake the layout scheme into a finalized ragged layout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layout is invalid, see `try_finish`.
+    #[must_use]
+    pub fn finish(self) -> RaggedBlockLayout {
+        match self.try_finish() {
+            Ok(layout) => layout,
+            Err(err) => panic!("invalid ragged block layout: {}", err),
+        }
+    }
+
+    /// Bake the layout scheme into a finalized ragged layout.
+    ///
+    /// Fails if a component alignment exceeds [`MAX_ALIGNMENT`](constant.MAX_ALIGNMENT.html)
+    /// or the size of the block overflows.
+    pub fn try_finish(self) -> Result<RaggedBlockLayout, LayoutError> {
+        let align = self
+            .fields
+            .iter()
+            .map(|(layout, _)| layout.align())
+            .max()
+            .unwrap_or(1);
+        if align > MAX_ALIGNMENT {
+            return Err(LayoutError::InvalidAlignment(align));
+        }
+
+        // Descending alignment keeps all arrays aligned without padding, as in `LayoutBuilder`.
+        let mut order = (0..self.fields.len()).collect::<Vec<_>>();
+        order.sort_by_key(|slot| Reverse(self.fields[*slot].0.align()));
+
+        let mut offsets = vec![0; self.fields.len()];
+        let mut size = 0usize;
+        for slot in order {
+            let (layout, count) = self.fields[slot];
+            offsets[slot] = size;
+            size = layout
+                .size()
+                .checked_mul(count)
+                .and_then(|bytes| size.checked_add(bytes))
+                .ok_or(LayoutError::Overflow)?;
+        }
+
+        Ok(RaggedBlockLayout {
+            layout: Layout::from_size_align(size, align).map_err(|_| LayoutError::Overflow)?,
+            fields: self.fields,
+            offsets,
+        })
+    }
+}
+
+/// SoA layout definition with per-component element counts
+///
+/// Unlike [`BlockLayout`](struct.BlockLayout.html) the arrays of the components don't
+/// share a common length. Each component has a fixed number of elements, which allows
+/// to store sparse or optional attributes densely next to the other components.
+///
+/// # Examples
+///
+/// ```rust
+/// # use norse_billow::RaggedBlockLayout;
+/// # use std::ptr::NonNull;
+/// let mut layout = RaggedBlockLayout::build();
+/// let position = layout.add::<[f32; 3]>(16);
+/// let name = layout.add::<u32>(4);
+/// let ragged_layout = layout.finish();
+/// assert_eq!(ragged_layout.layout().size(), 16 * 12 + 4 * 4);
+///
+/// let mut data = [0u32; 52];
+/// let block = ragged_layout
+///     .apply(NonNull::new(data.as_mut_ptr() as *mut u8).unwrap(), 208)
+///     .unwrap();
+/// unsafe {
+///     assert_eq!(block.as_slice::<[f32; 3]>(position).len(), 16);
+///     assert_eq!(block.as_slice::<u32>(name).len(), 4);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedBlockLayout {
+    layout: Layout,
+    fields: Vec<(Layout, usize)>,
+    /// Offsets of the component arrays, indexed by layout slot.
+    offsets: Vec<usize>,
+}
+
+impl RaggedBlockLayout {
+    /// Build a new ragged block layout.
+    #[must_use]
+    pub fn build() -> RaggedLayoutBuilder {
+        RaggedLayoutBuilder { fields: Vec::new() }
+    }
+
+    /// Returns the layout of a memory block holding all components.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the number of elements of a component.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by this layout.
+    pub fn len(&self, slot: LayoutSlot) -> usize {
+        self.field(slot, "RaggedBlockLayout::len").1
+    }
+
+    /// Apply the ragged layout to a memory region.
+    ///
+    /// The start of the memory region is rounded up to the alignment of the layout.
+    /// Fails with `LayoutError::SizeTooSmall` if the memory region can't hold all components.
+    pub fn apply(&self, data: NonNull<u8>, size: usize) -> Result<RaggedBlock, LayoutError> {
+        let align = self.layout.align();
+        let ptr = data.as_ptr() as usize;
+        let start = ptr + align_offset(data, align);
+        let required = self.layout.size() + (start - ptr);
+        if size < required {
+            return Err(LayoutError::SizeTooSmall {
+                required,
+                found: size,
+            });
+        }
+
+        let slices = self
+            .fields
+            .iter()
+            .zip(&self.offsets)
+            .map(|(&(layout, count), &offset)| {
+                let field = data.as_ptr().wrapping_add(start - ptr + offset);
+                (NonNull::new(field).unwrap(), layout, count)
+            })
+            .collect();
+        Ok(RaggedBlock {
+            range: start - ptr..required,
+            slices,
+        })
+    }
+
+    fn field(&self, slot: LayoutSlot, method: &str) -> (Layout, usize) {
+        match self.fields.get(slot) {
+            Some(field) => *field,
+            None => panic!("{}: invalid layout slot {}", method, slot),
+        }
+    }
+}
+
+/// Laid out memory block of a ragged layout
+///
+/// Obtained from [`RaggedBlockLayout::apply`](struct.RaggedBlockLayout.html#method.apply).
+/// Doesn't own or borrow the memory region, the validity requirements of
+/// [`Block`](struct.Block.html) apply.
+pub struct RaggedBlock {
+    range: Range<usize>,
+    slices: Vec<(NonNull<u8>, Layout, usize)>,
+}
+
+impl RaggedBlock {
+    /// Returns the offset range which denotes the occupied memory block.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the number of components in the block.
+    pub fn num_fields(&self) -> usize {
+        self.slices.len()
+    }
+
+    /// Returns the number of elements of a component.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding layout.
+    pub fn len(&self, slot: LayoutSlot) -> usize {
+        self.slice(slot, "RaggedBlock::len").2
+    }
+
+    /// Get the raw pointer and len for a component slot.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding layout.
+    pub unsafe fn as_raw<T>(&self, slot: LayoutSlot) -> (*mut T, usize) {
+        let (ptr, _, count) = self.slice(slot, "RaggedBlock::as_raw");
+        (ptr.cast::<T>().as_ptr(), count)
+    }
+
+    /// Get the mutable slice for a component slot.
+    ///
+    /// # Safety
+    ///
+    /// The type `T` **must** match the type used on `add` for the passed slot.
+    /// All values in the resulting slice are undefined!
+    ///
+    /// The returned slice aliases the block memory. Retrieving the same slot
+    /// multiple times concurrently results in aliasing mutable references.
+    ///
+    /// # Panics
+    ///
+    /// `slot` must be a valid value obtained by the corresponding layout.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_slice<T: Copy>(&self, slot: LayoutSlot) -> &mut [T] {
+        let (ptr, layout, count) = self.slice(slot, "RaggedBlock::as_slice");
+        debug_assert_eq!(mem::size_of::<T>(), layout.size());
+        slice::from_raw_parts_mut(ptr.cast::<T>().as_ptr(), count)
+    }
+
+    fn slice(&self, slot: LayoutSlot, method: &str) -> (NonNull<u8>, Layout, usize) {
+        match self.slices.get(slot) {
+            Some(slice) => *slice,
+            None => panic!(
+                "{}: invalid layout slot {}: block has {} fields",
+                method,
+                slot,
+                self.slices.len()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ragged() {
+        let mut layout = RaggedBlockLayout::build();
+        let a = layout.add::<u8>(5);
+        let b = layout.add::<u64>(2);
+        let c = layout.add::<u16>(3);
+        let ragged_layout = layout.finish();
+        assert_eq!(ragged_layout.layout().size(), 5 + 16 + 6);
+        assert_eq!(ragged_layout.layout().align(), 8);
+        assert_eq!(ragged_layout.len(c), 3);
+
+        let mut data = [0u64; 8];
+        let base = data.as_mut_ptr() as *mut u8;
+        let ptr = NonNull::new(unsafe { base.add(1) }).unwrap();
+        assert_eq!(
+            ragged_layout.apply(ptr, 30).err(),
+            Some(LayoutError::SizeTooSmall {
+                required: 34,
+                found: 30
+            })
+        );
+
+        let block = ragged_layout.apply(ptr, 63).unwrap();
+        assert_eq!(block.range(), 7..34);
+        unsafe {
+            block.as_slice::<u8>(a).fill(1);
+            block.as_slice::<u64>(b).fill(!0);
+            block.as_slice::<u16>(c).fill(2);
+            assert_eq!(block.as_slice::<u8>(a), [1; 5]);
+            assert_eq!(block.as_slice::<u64>(b), [!0; 2]);
+            assert_eq!(block.as_slice::<u16>(c), [2; 3]);
+            assert_eq!(block.as_raw::<u64>(b).0 as usize % 8, 0);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{Block, BlockLayout};
+use std::alloc::{self, Layout};
+use std::ptr::{self, NonNull};
+
+/// Growable SoA container.
+///
+/// Owns a memory block laid out according to a [`BlockLayout`](struct.BlockLayout.html)
+/// and tracks the number of initialized elements. The capacity grows on demand,
+/// reallocating the block and moving all components into the new memory.
+///
+/// Components are treated as plain bytes, no destructors will be run for elements.
+pub struct SoaVec {
+    layout: BlockLayout,
+    memory: Option<(NonNull<u8>, Layout)>,
+    block: Block,
+    len: usize,
+}
+
+impl SoaVec {
+    /// Create a new empty container for the passed layout.
+    ///
+    /// Doesn't allocate until elements are pushed.
+    pub fn new(layout: BlockLayout) -> Self {
+        let block = layout.apply(Self::dangling(&layout), 0);
+        SoaVec {
+            layout,
+            memory: None,
+            block,
+            len: 0,
+        }
+    }
+
+    /// Create a new empty container with space for at least `capacity` elements.
+    pub fn with_capacity(layout: BlockLayout, capacity: usize) -> Self {
+        let mut vec = Self::new(layout);
+        vec.reserve(capacity);
+        vec
+    }
+
+    /// Returns the number of elements in the container.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the container holds no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the container can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        if self.layout.layout().size() == 0 {
+            !0
+        } else {
+            self.block.len()
+        }
+    }
+
+    /// Returns the layout of the container.
+    pub fn layout(&self) -> &BlockLayout {
+        &self.layout
+    }
+
+    /// Returns the underlying memory block.
+    ///
+    /// The slices of the block span the whole capacity, only the first `len()`
+    /// elements are initialized.
+    pub fn block(&self) -> &Block {
+        &self.block
+    }
+
+    /// Reserve capacity for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        if required <= self.capacity() {
+            return;
+        }
+
+        let capacity = required.max(2 * self.capacity());
+        self.grow(capacity);
+    }
+
+    /// Append a new element with undefined values.
+    ///
+    /// Returns the index of the new element, which can be used to write the components.
+    pub fn push_uninit(&mut self) -> usize {
+        self.reserve(1);
+        let index = self.len;
+        self.len += 1;
+        index
+    }
+
+    /// Shorten the container to `len` elements.
+    ///
+    /// Has no effect if `len` is greater than the current length.
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    /// Remove all elements.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Remove the element at `index` by swapping in the last element.
+    ///
+    /// This doesn't preserve ordering but is O(1).
+    ///
+    /// # Panics
+    ///
+    /// `index` must be smaller than the number of elements.
+    pub fn swap_remove(&mut self, index: usize) {
+        assert!(index < self.len, "swap_remove index out of bounds");
+        let last = self.len - 1;
+        // The block covers the owned buffer of the vector.
+        unsafe { self.block.swap_elements(index, last) };
+        self.len = last;
+    }
+
+    /// Call `f` for every element in order.
+    ///
+    /// The closure receives the block and the index of the element, only the
+    /// `len()` initialized elements are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use norse_billow::{BlockLayout, SoaVec};
+    /// let mut layout = BlockLayout::build();
+    /// let value = layout.add::<u32>();
+    /// let mut vec = SoaVec::new(layout.finish());
+    /// for i in 0..4 {
+    ///     let index = vec.push_uninit();
+    ///     unsafe { vec.block().as_slice::<u32>(value)[index] = i };
+    /// }
+    ///
+    /// let mut sum = 0;
+    /// vec.for_each(|block, i| sum += unsafe { block.as_slice::<u32>(value)[i] });
+    /// assert_eq!(sum, 6);
+    /// ```
+    pub fn for_each<F>(&self, mut f: F)
+    where
+        F: FnMut(&Block, usize),
+    {
+        for i in 0..self.len {
+            f(&self.block, i);
+        }
+    }
+
+    /// Retain only the elements for which `f` returns `true`.
+    ///
+    /// The closure is called once for every element in order with the block
+    /// and the current index of the element. Surviving elements are compacted
+    /// preserving their relative order.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Block, usize) -> bool,
+    {
+        let mut len = 0;
+        for i in 0..self.len {
+            if f(&self.block, i) {
+                self.block.copy_element(i, len);
+                len += 1;
+            }
+        }
+        self.len = len;
+    }
+
+    fn grow(&mut self, capacity: usize) {
+        let layout = self
+            .layout
+            .block_layout(capacity)
+            .expect("capacity overflow");
+
+        let memory = unsafe { alloc::alloc(layout) };
+        let memory = NonNull::new(memory).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        let block = unsafe { self.layout.apply_poisoned(memory, layout.size()) };
+        debug_assert!(block.len() >= capacity);
+
+        for (dst, src) in block.slices.iter().zip(&self.block.slices) {
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    src.ptr.as_ptr(),
+                    dst.ptr.as_ptr(),
+                    src.layout.size() * self.len,
+                );
+            }
+        }
+
+        if let Some((memory, layout)) = self.memory.take() {
+            unsafe { alloc::dealloc(memory.as_ptr(), layout) };
+        }
+        self.memory = Some((memory, layout));
+        self.block = block;
+    }
+
+    fn dangling(layout: &BlockLayout) -> NonNull<u8> {
+        NonNull::new(ptr::without_provenance_mut(layout.layout().align())).unwrap()
+    }
+}
+
+impl Drop for SoaVec {
+    fn drop(&mut self) {
+        if let Some((memory, layout)) = self.memory.take() {
+            unsafe { alloc::dealloc(memory.as_ptr(), layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn push(vec: &mut SoaVec, slots: (usize, usize), value: u32) {
+        let i = vec.push_uninit();
+        unsafe {
+            vec.block().as_slice::<u32>(slots.0)[i] = value;
+            vec.block().as_slice::<u8>(slots.1)[i] = value as u8;
+        }
+    }
+
+    fn values(vec: &SoaVec, slots: (usize, usize)) -> Vec<(u32, u8)> {
+        unsafe {
+            let a = &vec.block().as_slice::<u32>(slots.0)[..vec.len()];
+            let b = &vec.block().as_slice::<u8>(slots.1)[..vec.len()];
+            a.iter().copied().zip(b.iter().copied()).collect()
+        }
+    }
+
+    fn vec() -> (SoaVec, (usize, usize)) {
+        let mut layout = BlockLayout::build();
+        let b = layout.add::<u8>();
+        let a = layout.add::<u32>();
+        (SoaVec::new(layout.finish()), (a, b))
+    }
+
+    #[test]
+    fn grow() {
+        let (mut vec, slots) = vec();
+        for i in 0..100 {
+            push(&mut vec, slots, i);
+        }
+        assert!(vec.capacity() >= 100);
+        assert_eq!(
+            values(&vec, slots),
+            (0..100).map(|i| (i, i as u8)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn swap_remove() {
+        let (mut vec, slots) = vec();
+        for i in 0..4 {
+            push(&mut vec, slots, i);
+        }
+        vec.swap_remove(1);
+        assert_eq!(values(&vec, slots), [(0, 0), (3, 3), (2, 2)]);
+        vec.swap_remove(2);
+        assert_eq!(values(&vec, slots), [(0, 0), (3, 3)]);
+    }
+
+    #[test]
+    fn retain() {
+        let (mut vec, slots) = vec();
+        for i in 0..10 {
+            push(&mut vec, slots, i);
+        }
+        vec.retain(|block, i| unsafe { block.as_slice::<u32>(slots.0)[i] % 3 == 0 });
+        assert_eq!(values(&vec, slots), [(0, 0), (3, 3), (6, 6), (9, 9)]);
+    }
+}